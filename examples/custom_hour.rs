@@ -1,4 +1,4 @@
-use islam::jiff;
+use islam::jiff::{self, tz::TimeZone};
 use islam::salah::{Config, Location, Madhab, Method, PrayerSchedule};
 
 fn custom_hour() -> Result<(), islam::Error> {
@@ -9,7 +9,7 @@ fn custom_hour() -> Result<(), islam::Error> {
         // .at(20, 28, 00, 0);
         // Current prayer is ishaa (after midnight/early moring, before fajr)
         .at(4, 28, 00, 0);
-    let prayer_times = PrayerSchedule::new(central_jakarta)
+    let prayer_times = PrayerSchedule::new(central_jakarta, TimeZone::get("Asia/Jakarta")?)
         .at(now)
         .with_config(config)
         .calculate()?;
@@ -30,7 +30,7 @@ fn custom_hour() -> Result<(), islam::Error> {
     println!("\nCurrent Prayer");
     println!(
         "{}: ({:02}:{:02} left)",
-        current_prayer.name(),
+        prayer_times.name_of(current_prayer),
         hour,
         minute
     );
@@ -39,7 +39,7 @@ fn custom_hour() -> Result<(), islam::Error> {
     let next_prayer = prayer_times.next();
     let time = prayer_times.time(next_prayer);
     let time = time.strftime("%H:%M").to_string();
-    println!("{}: ({})", next_prayer.name(), time);
+    println!("{}: ({})", prayer_times.name_of(next_prayer), time);
 
     Ok(())
 }
@@ -0,0 +1,30 @@
+use islam::jiff::civil;
+use islam::qibla::Qibla;
+use islam::salah::Location;
+
+fn example() -> Result<(), islam::Error> {
+    let central_jakarta = Location::new(-6.17, 106.82);
+    let qibla = Qibla::new(central_jakarta);
+
+    println!("Qibla direction: {:.2}° from true north", qibla.direction());
+    println!("Compass bearing: {:.2}°", qibla.compass(0.8));
+    println!("Distance to the Kaaba: {:.0} km", qibla.distance());
+
+    println!("\nRasd al-Qibla");
+    for rasd in qibla.rasd(civil::date(2025, 1, 1), civil::date(2025, 12, 31))? {
+        println!("{}: {:?}", rasd.time, rasd.shadow);
+    }
+
+    println!("\nToday's qibla shadow");
+    for shadow in qibla.shadow_times(islam::jiff::Zoned::now().date())? {
+        println!("{}: {:?}", shadow.time, shadow.shadow);
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = example() {
+        eprintln!("Error: {:?}", err);
+    }
+}
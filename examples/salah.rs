@@ -1,3 +1,4 @@
+use islam::jiff::tz::TimeZone;
 use islam::salah::{Config, Location, Madhab, Method, PrayerSchedule};
 
 fn example() -> Result<(), islam::Error> {
@@ -10,7 +11,7 @@ fn example() -> Result<(), islam::Error> {
     // .on(now)?
     // .with_config(config)
     // ```
-    let prayer_times = PrayerSchedule::new(central_jakarta)
+    let prayer_times = PrayerSchedule::new(central_jakarta, TimeZone::get("Asia/Jakarta")?)
         .with_config(config)
         .calculate()?;
 
@@ -59,7 +60,7 @@ fn example() -> Result<(), islam::Error> {
     println!("\nCurrent Prayer");
     println!(
         "{}: ({:02}:{:02} left)",
-        current_prayer.name(),
+        prayer_times.name_of(current_prayer),
         hour,
         minute
     );
@@ -68,7 +69,7 @@ fn example() -> Result<(), islam::Error> {
     let next_prayer = prayer_times.next();
     let time = prayer_times.time(next_prayer);
     let time = time.strftime("%H:%M").to_string();
-    println!("{}: ({})", next_prayer.name(), time);
+    println!("{}: ({})", prayer_times.name_of(next_prayer), time);
 
     Ok(())
 }
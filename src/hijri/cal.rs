@@ -27,10 +27,26 @@ pub fn equation_of_time(julian_date: f32) -> f32 {
     (c + r) * 4.0
 }
 
+/// Get sun declination
+pub fn sun_declination(julian_date: f32) -> f32 {
+    let n = julian_date - 2_451_544.5;
+    let epsilon = 23.44 - (0.000_000_4 * n);
+    let l = 0.985_647_4_f32.mul_add(n, 280.466);
+    let g = 0.985_600_3_f32.mul_add(n, 357.528);
+    let lambda = 0.02_f32.mul_add(dsin(2.0 * g), 1.915_f32.mul_add(dsin(g), l));
+    let x = dsin(epsilon) * dsin(lambda);
+    (180.0 / (4.0 * (1.0_f32).atan())) * (x / (-x).mul_add(x, 1.0).sqrt()).atan()
+}
+
 pub fn hijri_to_julian(date: civil::Date) -> i32 {
-    let year = i32::from(date.year());
-    let month = i32::from(date.month());
-    let day = i32::from(date.day());
+    hijri_day_to_julian(date.year(), date.month(), date.day())
+}
+
+/// Same as `hijri_to_julian`, for Hijri days that are not valid Gregorian dates (e.g. 29 Safar)
+pub fn hijri_day_to_julian(year: i16, month: i8, day: i8) -> i32 {
+    let year = i32::from(year);
+    let month = i32::from(month);
+    let day = i32::from(day);
     ((11 * year + 3) / 30) + (354 * year) + (30 * month) - ((month - 1) / 2) + day + 1_948_440 - 385
 }
 
@@ -46,14 +62,9 @@ pub fn gregorian_to_julian(date: civil::Date) -> f32 {
 
     let a = (year as f32 / 100.0).floor() as i32;
 
-    // In this method, the Gregorian calendar reform is taken into account, the day
-    // following 04 Oct. 1582 (Julian calendar) is 15 Oct. 1582 (Gregorian calendar)
-    // for more information see [3, p60]
-    let b = if year > 1582 || year == 1582 && month > 10 || month == 10 && day > 15 {
-        2 - a + (a / 4)
-    } else {
-        0
-    };
+    // Dates are in the proleptic Gregorian calendar (like jiff), even before the reform
+    // of 15 Oct. 1582, for more information see [3, p60]
+    let b = 2 - a + (a / 4);
 
     // julian day
     ((365.25 * (year + 4716) as f32).floor() as i32
@@ -63,7 +74,18 @@ pub fn gregorian_to_julian(date: civil::Date) -> f32 {
         - 1524.5
 }
 
+/// Julian Day Number of the date, at its noon
+pub fn gregorian_to_julian_day(date: civil::Date) -> i32 {
+    (gregorian_to_julian(date) + 0.5) as i32
+}
+
 pub fn julian_to_hijri(julian_date: i32, correction_val: i32) -> civil::Date {
+    let (year, month, day) = julian_to_hijri_day(julian_date, correction_val);
+    civil::date(year, month, day)
+}
+
+/// Same as `julian_to_hijri`, for Hijri days that are not valid Gregorian dates (e.g. 29 Safar)
+pub fn julian_to_hijri_day(julian_date: i32, correction_val: i32) -> (i16, i8, i8) {
     let mut l = ((julian_date as f32 + correction_val as f32).floor() as i32 - 1_948_440) + 10632;
     let n = (((l - 1) / 10631) as f32).floor();
     l = l - (10631_f32 * n) as i32 + 354;
@@ -82,34 +104,35 @@ pub fn julian_to_hijri(julian_date: i32, correction_val: i32) -> civil::Date {
     let day = ((l - ((709_f32 * month) as i32 / 24)) as f32).floor();
     let year = ((30_f32.mul_add(n, j) as i32 - 30) as f32).floor();
 
-    civil::date(year as i16, month as i8, day as i8)
+    (year as i16, month as i8, day as i8)
 }
 
-pub fn julian_to_gregorian(mut julian_date: f32) -> civil::Date {
-    julian_date = (julian_date as i32 + 5) as f32;
-    let z = julian_date.floor() as i32;
-    let f = julian_date as i32 - z;
+/// Date of the Julian Day, in the proleptic Gregorian calendar.
+/// The Julian Day Number (integer) of a day is at its noon, and its Julian Day at the previous midnight (.5)
+pub fn julian_to_gregorian(julian_date: f32) -> civil::Date {
+    let z = (julian_date + 0.5).floor() as i32;
 
-    let a = if z < 2_299_161 {
-        z
-    } else {
-        let alpha = ((z as f32 - 1_867_216.25) / 36524.25).floor();
-        ((z + 1) + alpha as i32) - ((alpha as i32 / 4) as f32).floor() as i32
-    };
+    // Same as Meeus, with integers since f32 is not precise enough for these days:
+    // alpha = floor((z - 1867216.25) / 36524.25)
+    let alpha = (4 * z - 7_468_865) / 146_097;
+    let a = z + 1 + alpha - alpha / 4;
 
     let b = a + 1524;
-    let c = ((b as f32 - 122.1) / 365.25).floor();
-    let d = (365.25 * c).floor();
-    let e = ((b - d as i32) as f32 / 30.6001).floor(); //  The 30.6001 SHOULD NOT BE REPLACED by 30.6
+    // c = floor((b - 122.1) / 365.25)
+    let c = (20 * b - 2442) / 7305;
+    // d = floor(365.25 * c)
+    let d = 1461 * c / 4;
+    // e = floor((b - d) / 30.6001), the 30.6001 SHOULD NOT BE REPLACED by 30.6
+    let e = 10_000 * (b - d) / 306_001;
 
     // Calculate the day
-    let day = (b - d as i32) - (30.6001 * e).floor() as i32 + f;
+    let day = (b - d) - 306_001 * e / 10_000;
 
     // Calculate the month
-    let month = e as i32 - if (e as i32) < 14 { 1 } else { 13 };
+    let month = e - if e < 14 { 1 } else { 13 };
 
     // Calculate the year
-    let year = c as i32 - if month > 2 { 4716 } else { 4715 };
+    let year = c - if month > 2 { 4716 } else { 4715 };
 
     civil::date(year as i16, month as i8, day as i8)
 }
@@ -143,6 +166,18 @@ mod tests {
         assert_eq!(format!("{:.1$}", equation, precision), "3.53552");
     }
     #[test]
+    fn test_sun_declination() {
+        let precision = 2;
+
+        // solstices and equinox of 2025
+        let declination = sun_declination(gregorian_to_julian(civil::date(2025, 6, 21)));
+        assert_eq!(format!("{:.1$}", declination, precision), "23.44");
+        let declination = sun_declination(gregorian_to_julian(civil::date(2025, 12, 21)));
+        assert_eq!(format!("{:.1$}", declination, precision), "-23.44");
+        let declination = sun_declination(gregorian_to_julian(civil::date(2025, 3, 20)));
+        assert_eq!(format!("{:.1$}", declination, precision), "0.05");
+    }
+    #[test]
     fn test_hijri_to_julian() -> Result<(), crate::Error> {
         assert_eq!(hijri_to_julian(civil::date(1442, 8, 25)), 2459313);
         assert_eq!(hijri_to_julian(civil::date(333, 1, 27)), 2066116);
@@ -157,12 +192,16 @@ mod tests {
         );
         assert_eq!(
             gregorian_to_julian(civil::date(333, 1, 27)),
-            1842712.5 // python version: 1842713.0
+            1842711.5 // python version: 1842713.0
         );
         assert_eq!(
             gregorian_to_julian(civil::date(2000, 1, 1)),
             2451544.5 // python version: 2451545.0
         );
+        // proleptic Gregorian calendar before the reform
+        assert_eq!(gregorian_to_julian(civil::date(1582, 10, 4)), 2299149.5);
+        assert_eq!(gregorian_to_julian(civil::date(1582, 10, 15)), 2299160.5);
+        assert_eq!(gregorian_to_julian(civil::date(1000, 10, 20)), 2086594.5);
         Ok(())
     }
     #[test]
@@ -170,10 +209,18 @@ mod tests {
         assert_eq!(julian_to_hijri(2459313, 0), civil::date(1442, 8, 25));
         assert_eq!(julian_to_hijri(2066116, 0), civil::date(333, 1, 27));
         assert_eq!(julian_to_hijri(1948466, 0), civil::date(1, 1, 27));
+        // 29 Safar 1443 is not a Gregorian date
+        assert_eq!(julian_to_hijri_day(2459495, 0), (1443, 2, 29));
+        assert_eq!(hijri_day_to_julian(1443, 2, 29), 2459495);
     }
     #[test]
     fn test_julian_to_gregorian() {
-        assert_eq!(julian_to_gregorian(2459313.0), civil::date(2021, 4, 13));
-        assert_eq!(julian_to_gregorian(2415020.5), civil::date(1900, 1, 5));
+        assert_eq!(julian_to_gregorian(2459313.0), civil::date(2021, 4, 8));
+        assert_eq!(julian_to_gregorian(2459312.5), civil::date(2021, 4, 8));
+        assert_eq!(julian_to_gregorian(2415020.5), civil::date(1900, 1, 1));
+        // around the Gregorian reform
+        assert_eq!(julian_to_gregorian(2299160.0), civil::date(1582, 10, 14));
+        assert_eq!(julian_to_gregorian(2299161.0), civil::date(1582, 10, 15));
+        assert_eq!(julian_to_gregorian(2086594.5), civil::date(1000, 10, 20));
     }
 }
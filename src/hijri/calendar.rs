@@ -0,0 +1,168 @@
+use jiff::civil;
+
+use crate::hijri::{
+    HijriDate,
+    cal::gregorian_to_julian_day,
+    tabular::{Epoch, Kuwaiti, LeapYears, Tabular},
+    umm_al_qura::UmmAlQura,
+};
+
+/// Conversions of a Hijri calendar, from and to the Julian Day Number
+pub trait HijriCalendar {
+    /// Julian Day Number of the Hijri date
+    fn to_julian_day(&self, year: i16, month: i8, day: i8) -> Result<i32, crate::Error>;
+    /// Hijri date of the Julian Day Number, as (year, month, day)
+    #[allow(clippy::wrong_self_convention)]
+    fn from_julian_day(&self, julian_day: i32) -> Result<(i16, i8, i8), crate::Error>;
+    /// Number of days of the month
+    fn month_length(&self, year: i16, month: i8) -> Result<i8, crate::Error>;
+    /// Whether the year has 355 days
+    fn is_leap_year(&self, year: i16) -> Result<bool, crate::Error>;
+}
+
+/// Hijri calendar used to convert Gregorian dates
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Calendar {
+    /// Arithmetical (tabular) calendar, with its leap years and epoch
+    Tabular(LeapYears, Epoch),
+    /// Kuwaiti algorithm (Microsoft)
+    Kuwaiti,
+    /// Official calendar of Saudi Arabia, from 1300 to 1600 AH
+    UmmAlQura,
+}
+
+impl Default for Calendar {
+    /// The most common tabular calendar
+    fn default() -> Self {
+        Self::Tabular(LeapYears::Type16, Epoch::Civil)
+    }
+}
+
+impl Calendar {
+    /// Hijri date of the Gregorian date, `correction` is in days
+    pub fn from_gregorian(
+        self,
+        date: civil::Date,
+        correction: i32,
+    ) -> Result<HijriDate, crate::Error> {
+        HijriDate::from_julian_with(gregorian_to_julian_day(date) + correction, &self)
+    }
+    /// Gregorian date of the Hijri date
+    pub fn to_gregorian(self, date: &HijriDate) -> Result<civil::Date, crate::Error> {
+        date.to_gregorian_with(&self)
+    }
+}
+
+impl HijriCalendar for Calendar {
+    fn to_julian_day(&self, year: i16, month: i8, day: i8) -> Result<i32, crate::Error> {
+        match self {
+            Self::Tabular(leap_years, epoch) => {
+                Tabular::new(*leap_years, *epoch).to_julian_day(year, month, day)
+            }
+            Self::Kuwaiti => Kuwaiti.to_julian_day(year, month, day),
+            Self::UmmAlQura => UmmAlQura.to_julian_day(year, month, day),
+        }
+    }
+    fn from_julian_day(&self, julian_day: i32) -> Result<(i16, i8, i8), crate::Error> {
+        match self {
+            Self::Tabular(leap_years, epoch) => {
+                Tabular::new(*leap_years, *epoch).from_julian_day(julian_day)
+            }
+            Self::Kuwaiti => Kuwaiti.from_julian_day(julian_day),
+            Self::UmmAlQura => UmmAlQura.from_julian_day(julian_day),
+        }
+    }
+    fn month_length(&self, year: i16, month: i8) -> Result<i8, crate::Error> {
+        match self {
+            Self::Tabular(leap_years, epoch) => {
+                Tabular::new(*leap_years, *epoch).month_length(year, month)
+            }
+            Self::Kuwaiti => Kuwaiti.month_length(year, month),
+            Self::UmmAlQura => UmmAlQura.month_length(year, month),
+        }
+    }
+    fn is_leap_year(&self, year: i16) -> Result<bool, crate::Error> {
+        match self {
+            Self::Tabular(leap_years, epoch) => {
+                Tabular::new(*leap_years, *epoch).is_leap_year(year)
+            }
+            Self::Kuwaiti => Kuwaiti.is_leap_year(year),
+            Self::UmmAlQura => UmmAlQura.is_leap_year(year),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabular_with_correction() -> Result<(), crate::Error> {
+        // 1 Shawwal 1446 was announced on 2025-03-30
+        let date = civil::date(2025, 3, 30);
+        let hijri = Calendar::default().from_gregorian(date, 0)?;
+        assert_eq!((hijri.month, hijri.day), (9, 30));
+
+        let hijri = Calendar::default().from_gregorian(date, 1)?;
+        assert_eq!((hijri.month, hijri.day), (10, 1));
+        Ok(())
+    }
+    #[test]
+    fn umm_al_qura() -> Result<(), crate::Error> {
+        let hijri = Calendar::UmmAlQura.from_gregorian(civil::date(2025, 3, 30), 0)?;
+        assert_eq!((hijri.year, hijri.month, hijri.day), (1446, 10, 1));
+        assert_eq!(hijri.month_english, "Shawwal");
+
+        let hijri = Calendar::UmmAlQura.from_gregorian(civil::date(2021, 4, 9), 0)?;
+        assert_eq!((hijri.year, hijri.month, hijri.day), (1442, 8, 27));
+
+        let date = HijriDate::new(1446, 9, 1)?;
+        assert_eq!(
+            Calendar::UmmAlQura.to_gregorian(&date)?,
+            civil::date(2025, 3, 1)
+        );
+        Ok(())
+    }
+    #[test]
+    fn umm_al_qura_out_of_range() -> Result<(), crate::Error> {
+        assert!(
+            Calendar::UmmAlQura
+                .from_gregorian(civil::date(1882, 11, 11), 0)
+                .is_err()
+        );
+        assert!(
+            Calendar::UmmAlQura
+                .to_gregorian(&HijriDate::new(1601, 1, 1)?)
+                .is_err()
+        );
+        Ok(())
+    }
+    #[test]
+    fn tabular_variants() -> Result<(), crate::Error> {
+        // Same as Type15 leap years from the astronomical epoch
+        let type15 = Calendar::Tabular(LeapYears::Type15, Epoch::Astronomical);
+        let start = gregorian_to_julian_day(civil::date(2020, 1, 1));
+        for julian_day in start..start + 3653 {
+            assert_eq!(
+                Calendar::Kuwaiti.from_julian_day(julian_day)?,
+                type15.from_julian_day(julian_day)?
+            );
+        }
+        // The 7th year of the cycle is a leap year of Type16 only
+        let fatimid = Calendar::Tabular(LeapYears::Fatimid, Epoch::Civil);
+        assert!(Calendar::default().is_leap_year(1447)?);
+        assert!(!fatimid.is_leap_year(1447)?);
+        assert!(fatimid.is_leap_year(1448)?);
+        Ok(())
+    }
+    #[test]
+    fn leap_years() -> Result<(), crate::Error> {
+        assert!(Calendar::default().is_leap_year(1445)?);
+        assert!(!Calendar::default().is_leap_year(1446)?);
+        // 1448 has 355 days in the Umm al-Qura calendar only
+        assert!(!Calendar::default().is_leap_year(1448)?);
+        assert!(Calendar::UmmAlQura.is_leap_year(1448)?);
+        assert!(Calendar::UmmAlQura.is_leap_year(1601).is_err());
+        Ok(())
+    }
+}
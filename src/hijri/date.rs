@@ -1,7 +1,8 @@
 use jiff::civil;
 
-use crate::hijri::cal::{
-    gregorian_to_julian, hijri_to_julian, julian_to_gregorian, julian_to_hijri,
+use crate::hijri::{
+    cal::{gregorian_to_julian_day, hijri_day_to_julian, julian_to_gregorian, julian_to_hijri_day},
+    calendar::HijriCalendar,
 };
 use crate::time;
 
@@ -58,8 +59,7 @@ impl HijriDate {
         })
     }
     pub fn to_julian(&self) -> i32 {
-        let date = civil::date(self.year, self.month, self.day);
-        hijri_to_julian(date)
+        hijri_day_to_julian(self.year, self.month, self.day)
     }
     pub fn to_gregorian(&self) -> civil::Date {
         let julian = self.to_julian();
@@ -73,14 +73,14 @@ impl HijriDate {
         Self::from_gregorian(time::today(), correction_val)
     }
     pub fn from_julian(julian_date: i32, correction_val: i32) -> Self {
-        let date = julian_to_hijri(julian_date, correction_val);
+        let (year, month, day) = julian_to_hijri_day(julian_date, correction_val);
 
         Self {
-            year: date.year(),
-            month: date.month(),
-            day: date.day(),
-            month_arabic: Self::month_arabic(date.month()),
-            month_english: Self::month_english(date.month()),
+            year,
+            month,
+            day,
+            month_arabic: Self::month_arabic(month),
+            month_english: Self::month_english(month),
         }
     }
     fn month_arabic(month: i8) -> String {
@@ -90,15 +90,38 @@ impl HijriDate {
         ENGLISH_MONTHS[(month - 1) as usize].to_string()
     }
     pub fn from_gregorian(date: civil::Date, correction_val: i32) -> Self {
-        let date = julian_to_hijri(gregorian_to_julian(date) as i32, correction_val);
-
-        Self {
-            year: date.year(),
-            month: date.month(),
-            day: date.day(),
-            month_arabic: Self::month_arabic(date.month()),
-            month_english: Self::month_english(date.month()),
-        }
+        Self::from_julian(gregorian_to_julian_day(date), correction_val)
+    }
+    /// Julian Day Number of the date in the given calendar
+    pub fn to_julian_with<C: HijriCalendar>(&self, calendar: &C) -> Result<i32, crate::Error> {
+        calendar.to_julian_day(self.year, self.month, self.day)
+    }
+    /// Gregorian date of the date in the given calendar
+    pub fn to_gregorian_with<C: HijriCalendar>(
+        &self,
+        calendar: &C,
+    ) -> Result<civil::Date, crate::Error> {
+        let julian = self.to_julian_with(calendar)?;
+        Ok(julian_to_gregorian(julian as f32))
+    }
+    /// Date of the Julian Day Number in the given calendar
+    pub fn from_julian_with<C: HijriCalendar>(
+        julian_date: i32,
+        calendar: &C,
+    ) -> Result<Self, crate::Error> {
+        let (year, month, day) = calendar.from_julian_day(julian_date)?;
+        Self::new(year, month, day)
+    }
+    /// Date of the Gregorian date in the given calendar
+    pub fn from_gregorian_with<C: HijriCalendar>(
+        date: civil::Date,
+        calendar: &C,
+    ) -> Result<Self, crate::Error> {
+        Self::from_julian_with(gregorian_to_julian_day(date), calendar)
+    }
+    /// Next day in the given calendar
+    pub fn next_date_with<C: HijriCalendar>(&self, calendar: &C) -> Result<Self, crate::Error> {
+        Self::from_julian_with(self.to_julian_with(calendar)? + 1, calendar)
     }
 }
 
@@ -107,6 +130,11 @@ mod tests {
     use jiff::civil;
 
     use super::*;
+    use crate::hijri::{
+        cal::gregorian_to_julian,
+        tabular::{Epoch, Kuwaiti, LeapYears, Tabular},
+        umm_al_qura::UmmAlQura,
+    };
 
     fn hijri_date() -> Result<HijriDate, crate::Error> {
         HijriDate::new(1442, 8, 25)
@@ -133,13 +161,14 @@ mod tests {
     fn to_gregorian() -> Result<(), crate::Error> {
         let hijri_date = HijriDate::new(1442, 8, 25)?;
         let gregorian = hijri_date.to_gregorian();
-        assert_eq!(gregorian, civil::date(2021, 4, 13));
+        assert_eq!(gregorian, civil::date(2021, 4, 8));
         Ok(())
     }
     #[test]
     fn from_gregorian() -> Result<(), crate::Error> {
         let hijri_from_gregorian = HijriDate::from_gregorian(civil::date(2021, 4, 9), 0);
-        assert_eq!(hijri_from_gregorian.day, 25); // FIXME: this should be 27
+        // 27 in the Umm al-Qura calendar, see `Calendar::UmmAlQura`
+        assert_eq!(hijri_from_gregorian.day, 26);
         assert_eq!(hijri_from_gregorian.month, 8);
         assert_eq!(hijri_from_gregorian.month_arabic, "شعبان".to_string());
         assert_eq!(hijri_from_gregorian.month_english, "Shaban".to_string());
@@ -150,7 +179,8 @@ mod tests {
     fn from_gregorian_1() -> Result<(), crate::Error> {
         let hijri_from_gregorian = HijriDate::from_gregorian(civil::date(2020, 4, 18), 0);
         // tested against https://www.islamicfinder.org/islamic-calendar/2021/April/?type=Gregorian
-        assert_eq!(hijri_from_gregorian.day, 23); // FIXME: this should be 25
+        // 25 in the Umm al-Qura calendar
+        assert_eq!(hijri_from_gregorian.day, 24);
         assert_eq!(hijri_from_gregorian.month, 8);
         assert_eq!(hijri_from_gregorian.month_english, "Shaban".to_string());
         assert_eq!(hijri_from_gregorian.year, 1441);
@@ -167,6 +197,83 @@ mod tests {
         Ok(())
     }
     #[test]
+    fn tabular_references() -> Result<(), crate::Error> {
+        // Arithmetical (civil) calendar of Calendrical Calculations, Reingold & Dershowitz
+        let references = [
+            // 16 July 622 in the Julian calendar
+            ((1, 1, 1), civil::date(622, 7, 19)),
+            ((990, 12, 1), civil::date(1582, 12, 27)),
+            ((1000, 1, 1), civil::date(1591, 10, 19)),
+            ((1400, 1, 1), civil::date(1979, 11, 21)),
+            ((1446, 9, 1), civil::date(2025, 3, 1)),
+            ((1500, 1, 1), civil::date(2076, 11, 28)),
+            ((1600, 12, 30), civil::date(2174, 11, 25)),
+        ];
+        for ((year, month, day), gregorian) in references {
+            assert_eq!(HijriDate::new(year, month, day)?.to_gregorian(), gregorian);
+
+            let hijri = HijriDate::from_gregorian(gregorian, 0);
+            assert_eq!((hijri.year, hijri.month, hijri.day), (year, month, day));
+        }
+        Ok(())
+    }
+    #[test]
+    fn round_trip() -> Result<(), crate::Error> {
+        // Every day from 1 AH to 1600 AH, the Gregorian days follow each other
+        let mut previous = civil::date(622, 7, 18);
+        for year in 1..=1600 {
+            for month in 1..=12 {
+                let first = HijriDate::new(year, month, 1)?.to_julian();
+                let next = match month {
+                    12 => HijriDate::new(year + 1, 1, 1)?,
+                    _ => HijriDate::new(year, month + 1, 1)?,
+                };
+                for day in 1..=(next.to_julian() - first) as i8 {
+                    let gregorian = HijriDate::new(year, month, day)?.to_gregorian();
+                    assert_eq!(
+                        gregorian_to_julian(gregorian),
+                        gregorian_to_julian(previous) + 1.0
+                    );
+
+                    let hijri = HijriDate::from_gregorian(gregorian, 0);
+                    assert_eq!((hijri.year, hijri.month, hijri.day), (year, month, day));
+                    previous = gregorian;
+                }
+            }
+        }
+        assert_eq!(previous, civil::date(2174, 11, 25));
+        Ok(())
+    }
+    #[test]
+    fn calendars() -> Result<(), crate::Error> {
+        let date = civil::date(2021, 4, 9);
+        let day = |calendar: &dyn Fn() -> Result<HijriDate, crate::Error>| {
+            calendar().map(|hijri| (hijri.year, hijri.month, hijri.day))
+        };
+        assert_eq!(
+            day(&|| HijriDate::from_gregorian_with(date, &Tabular::default()))?,
+            (1442, 8, 26)
+        );
+        assert_eq!(
+            day(&|| HijriDate::from_gregorian_with(date, &UmmAlQura))?,
+            (1442, 8, 27)
+        );
+        assert_eq!(
+            day(&|| HijriDate::from_gregorian_with(date, &Kuwaiti))?,
+            (1442, 8, 27)
+        );
+        let fatimid = Tabular::new(LeapYears::Fatimid, Epoch::Astronomical);
+        let hijri = HijriDate::from_gregorian_with(date, &fatimid)?;
+        assert_eq!(hijri.to_gregorian_with(&fatimid)?, date);
+        assert_eq!(
+            hijri
+                .next_date_with(&fatimid)?
+                .to_gregorian_with(&fatimid)?,
+            civil::date(2021, 4, 10)
+        );
+        Ok(())
+    }
+    #[test]
     fn min_month() -> Result<(), crate::Error> {
         let hijri_date = HijriDate::new(1442, 1, 25)?;
         assert_eq!(hijri_date.month_arabic, "محرم".to_string());
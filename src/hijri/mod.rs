@@ -1,6 +1,12 @@
 #![allow(clippy::excessive_precision)]
 
 pub mod cal;
+pub mod calendar;
 pub mod date;
+pub mod tabular;
+pub mod umm_al_qura;
 
+pub use calendar::{Calendar, HijriCalendar};
 pub use date::HijriDate;
+pub use tabular::{Epoch, Kuwaiti, LeapYears, Tabular};
+pub use umm_al_qura::UmmAlQura;
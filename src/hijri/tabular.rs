@@ -0,0 +1,263 @@
+use crate::Error;
+use crate::hijri::calendar::HijriCalendar;
+
+/// Leap years (of 355 days) of the 30 years cycle
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum LeapYears {
+    /// 2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29
+    Type15,
+    /// 2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29, the most common one
+    Type16,
+    /// 2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29, Fatimid (Ismaili, Bohra) calendar
+    Fatimid,
+    /// 2, 5, 8, 11, 13, 16, 19, 21, 24, 27, 30, Habash al-Hasib and al-Biruni
+    HabashAlHasib,
+}
+
+impl LeapYears {
+    /// `(11 * year + shift) % 30 < 11` for a leap year
+    const fn shift(self) -> i32 {
+        match self {
+            Self::Type15 => 15,
+            Self::Type16 => 14,
+            Self::Fatimid => 11,
+            Self::HabashAlHasib => 9,
+        }
+    }
+}
+
+/// First day of the calendar, 1 Muharram 1 AH
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Epoch {
+    /// Friday 16 July 622 (Julian), JDN 1948440
+    Civil,
+    /// Thursday 15 July 622 (Julian), JDN 1948439
+    Astronomical,
+}
+
+impl Epoch {
+    const fn julian_day(self) -> i32 {
+        match self {
+            Self::Civil => 1_948_440,
+            Self::Astronomical => 1_948_439,
+        }
+    }
+}
+
+/// Arithmetical (tabular) calendar, months of 30 and 29 days in turn
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Tabular {
+    pub leap_years: LeapYears,
+    pub epoch: Epoch,
+}
+
+impl Default for Tabular {
+    fn default() -> Self {
+        Self::new(LeapYears::Type16, Epoch::Civil)
+    }
+}
+
+impl Tabular {
+    pub const fn new(leap_years: LeapYears, epoch: Epoch) -> Self {
+        Self { leap_years, epoch }
+    }
+    /// Days from the epoch to 1 Muharram
+    fn days_before_year(self, year: i32) -> i32 {
+        // leap years from 1 to `year - 1`
+        let leap_years = (11 * year + self.leap_years.shift() - 11).div_euclid(30);
+        354 * (year - 1) + leap_years
+    }
+}
+
+impl HijriCalendar for Tabular {
+    fn to_julian_day(&self, year: i16, month: i8, day: i8) -> Result<i32, Error> {
+        if !(1..=self.month_length(year, month)?).contains(&day) {
+            return Err(no_such_day(year, month, day));
+        }
+        let month = i32::from(month);
+        Ok(self.epoch.julian_day()
+            + self.days_before_year(i32::from(year))
+            + 29 * (month - 1)
+            + month / 2
+            + i32::from(day)
+            - 1)
+    }
+    fn from_julian_day(&self, julian_day: i32) -> Result<(i16, i8, i8), Error> {
+        let days = julian_day - self.epoch.julian_day();
+        // 10631 days in 30 years, then fixed by a year at most
+        let mut year = (30 * days + 10646).div_euclid(10631);
+        if self.days_before_year(year) > days {
+            year -= 1;
+        } else if self.days_before_year(year + 1) <= days {
+            year += 1;
+        }
+        let year = i16::try_from(year)
+            .map_err(|_| Error::InvalidArgument(format!("No such Julian Day: {julian_day}")))?;
+
+        let mut day_of_year = days - self.days_before_year(i32::from(year));
+        let mut month = 1;
+        while day_of_year >= i32::from(self.month_length(year, month)?) {
+            day_of_year -= i32::from(self.month_length(year, month)?);
+            month += 1;
+        }
+        Ok((year, month, day_of_year as i8 + 1))
+    }
+    fn month_length(&self, year: i16, month: i8) -> Result<i8, Error> {
+        match month {
+            12 if self.is_leap_year(year)? => Ok(30),
+            1..=12 => Ok(30 - (month + 1) % 2),
+            _ => Err(Error::InvalidMonth(month)),
+        }
+    }
+    fn is_leap_year(&self, year: i16) -> Result<bool, Error> {
+        Ok((11 * i32::from(year) + self.leap_years.shift()).rem_euclid(30) < 11)
+    }
+}
+
+/// Kuwaiti algorithm (Microsoft), computed with fractional years
+/// it is the same as the `Type15` leap years from the `Astronomical` epoch
+#[derive(PartialEq, Debug, Default, Copy, Clone)]
+pub struct Kuwaiti;
+
+impl Kuwaiti {
+    /// Julian Day Number before 1 Muharram 0 AH
+    const EPOCH: i32 = 1_948_084;
+    /// Mean year, in days
+    const YEAR: f64 = 10631.0 / 30.0;
+    const SHIFT: f64 = 8.01 / 60.0;
+
+    /// Days from the epoch to 1 Muharram
+    fn days_before_year(year: i16) -> i32 {
+        let year = i32::from(year);
+        let cycles = year.div_euclid(30);
+        let year = year.rem_euclid(30);
+        10631 * cycles + (f64::from(year) * Self::YEAR + Self::SHIFT).floor() as i32
+    }
+}
+
+impl HijriCalendar for Kuwaiti {
+    fn to_julian_day(&self, year: i16, month: i8, day: i8) -> Result<i32, Error> {
+        if !(1..=self.month_length(year, month)?).contains(&day) {
+            return Err(no_such_day(year, month, day));
+        }
+        let days_before_month = (29.5001 * f64::from(month) - 29.0).floor() as i32;
+        Ok(Self::EPOCH + Self::days_before_year(year) + days_before_month + i32::from(day))
+    }
+    fn from_julian_day(&self, julian_day: i32) -> Result<(i16, i8, i8), Error> {
+        let mut days = julian_day - Self::EPOCH;
+        let cycles = days.div_euclid(10631);
+        days -= 10631 * cycles;
+        let year = ((f64::from(days) - Self::SHIFT) / Self::YEAR).floor() as i32;
+        days -= (f64::from(year) * Self::YEAR + Self::SHIFT).floor() as i32;
+        let month = (((f64::from(days) + 28.5001) / 29.5).floor() as i32).min(12);
+        let day = days - (29.5001 * f64::from(month) - 29.0).floor() as i32;
+
+        let year = i16::try_from(30 * cycles + year)
+            .map_err(|_| Error::InvalidArgument(format!("No such Julian Day: {julian_day}")))?;
+        Ok((year, month as i8, day as i8))
+    }
+    fn month_length(&self, year: i16, month: i8) -> Result<i8, Error> {
+        match month {
+            12 if self.is_leap_year(year)? => Ok(30),
+            1..=12 => Ok(30 - (month + 1) % 2),
+            _ => Err(Error::InvalidMonth(month)),
+        }
+    }
+    fn is_leap_year(&self, year: i16) -> Result<bool, Error> {
+        Ok(Self::days_before_year(year + 1) - Self::days_before_year(year) == 355)
+    }
+}
+
+fn no_such_day(year: i16, month: i8, day: i8) -> Error {
+    Error::InvalidArgument(format!("No such Hijri day: {year}-{month}-{day}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hijri::cal::{hijri_day_to_julian, julian_to_hijri_day};
+
+    #[test]
+    fn leap_years() -> Result<(), Error> {
+        let cycle = |leap_years| {
+            let tabular = Tabular::new(leap_years, Epoch::Civil);
+            (1..=30)
+                .filter(|&year| tabular.is_leap_year(year).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            cycle(LeapYears::Type15),
+            [2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29]
+        );
+        assert_eq!(
+            cycle(LeapYears::Type16),
+            [2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29]
+        );
+        assert_eq!(
+            cycle(LeapYears::Fatimid),
+            [2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29]
+        );
+        assert_eq!(
+            cycle(LeapYears::HabashAlHasib),
+            [2, 5, 8, 11, 13, 16, 19, 21, 24, 27, 30]
+        );
+        assert_eq!(Tabular::default().month_length(1445, 12)?, 30);
+        assert_eq!(Tabular::default().month_length(1446, 12)?, 29);
+        Ok(())
+    }
+    #[test]
+    fn same_as_default_algorithm() -> Result<(), Error> {
+        let tabular = Tabular::default();
+        for julian_day in 1_948_440..2_520_000 {
+            let (year, month, day) = julian_to_hijri_day(julian_day, 0);
+            assert_eq!(tabular.from_julian_day(julian_day)?, (year, month, day));
+            assert_eq!(
+                tabular.to_julian_day(year, month, day)?,
+                hijri_day_to_julian(year, month, day)
+            );
+        }
+        Ok(())
+    }
+    #[test]
+    fn round_trip() -> Result<(), Error> {
+        for leap_years in [
+            LeapYears::Type15,
+            LeapYears::Type16,
+            LeapYears::Fatimid,
+            LeapYears::HabashAlHasib,
+        ] {
+            let tabular = Tabular::new(leap_years, Epoch::Astronomical);
+            let mut julian_day = tabular.to_julian_day(1, 1, 1)?;
+            assert_eq!(julian_day, 1_948_439);
+            for year in 1..=1600 {
+                for month in 1..=12 {
+                    for day in 1..=tabular.month_length(year, month)? {
+                        assert_eq!(tabular.to_julian_day(year, month, day)?, julian_day);
+                        assert_eq!(tabular.from_julian_day(julian_day)?, (year, month, day));
+                        julian_day += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    #[test]
+    fn kuwaiti() -> Result<(), Error> {
+        let type_15 = Tabular::new(LeapYears::Type15, Epoch::Astronomical);
+        for julian_day in 1_948_439..2_520_000 {
+            let date = Kuwaiti.from_julian_day(julian_day)?;
+            assert_eq!(date, type_15.from_julian_day(julian_day)?);
+            assert_eq!(Kuwaiti.to_julian_day(date.0, date.1, date.2)?, julian_day);
+        }
+        for year in 1..=30 {
+            assert_eq!(Kuwaiti.is_leap_year(year)?, type_15.is_leap_year(year)?);
+        }
+        Ok(())
+    }
+    #[test]
+    fn invalid_days() {
+        assert!(Tabular::default().to_julian_day(1446, 12, 30).is_err());
+        assert!(Tabular::default().to_julian_day(1446, 13, 1).is_err());
+        assert!(Kuwaiti.to_julian_day(1446, 2, 30).is_err());
+    }
+}
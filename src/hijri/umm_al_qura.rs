@@ -0,0 +1,190 @@
+use crate::Error;
+use crate::hijri::calendar::HijriCalendar;
+
+/// First year of the table
+pub const FIRST_YEAR: i16 = 1300;
+
+/// Last year of the table
+pub const LAST_YEAR: i16 = 1600;
+
+/// Julian Day Number of 1 Muharram 1300 (12 Nov. 1882)
+const FIRST_DAY: i32 = 2_408_762;
+
+/// Months of 30 days of each year since 1300 AH, the bit `n` is set when the month `n + 1` is long.
+/// Official Umm al-Qura calendar (KACST), from the table of ICU4C `islamcal.cpp`
+#[rustfmt::skip]
+const LONG_MONTHS: [u16; (LAST_YEAR - FIRST_YEAR + 1) as usize] = [
+    0x555, 0x2AB, 0x937, 0x2B6, 0x576, 0x36C, 0xB55, 0xAAA, 0x956, 0x49E,  // 1300
+    0x95D, 0x2BA, 0x5B5, 0x3AA, 0xB4B, 0xA96, 0x52E, 0x2AD, 0x56D, 0xB5A,  // 1310
+    0x752, 0xF25, 0xE8A, 0xD16, 0xA56, 0xAB5, 0x6B4, 0xDA9, 0xB92, 0xB25,  // 1320
+    0x64B, 0xA9B, 0x35A, 0x6D9, 0x5D4, 0xDA5, 0xD4A, 0xA95, 0x536, 0x975,  // 1330
+    0x2F4, 0x6E9, 0x6D4, 0x6A9, 0x535, 0x25D, 0x4BD, 0x9BA, 0x3B4, 0xB69,  // 1340
+    0xB2A, 0xA55, 0x4AD, 0xA5D, 0x2DA, 0x6D9, 0xEAA, 0xE94, 0xD2A, 0xC56,  // 1350
+    0x4AE, 0xA6D, 0x56A, 0xD55, 0xD4A, 0xA93, 0x52B, 0xA5B, 0x53A, 0x6B5,  // 1360
+    0xEA9, 0xD52, 0xD29, 0xA55, 0x4AD, 0x56D, 0xAEA, 0x6E4, 0xED1, 0xDA2,  // 1370
+    0xAAA, 0x95A, 0x2DA, 0x5B9, 0xBB2, 0x764, 0x6C9, 0x555, 0x2AB, 0x4DB,  // 1380
+    0xABA, 0x5B4, 0xDA9, 0xD52, 0xAA5, 0x92D, 0x26D, 0x8ED, 0x2DA, 0xAD5,  // 1390
+    0xAA5, 0xA4B, 0x497, 0x937, 0x2B6, 0x975, 0xD69, 0xD52, 0xC95, 0x92B,  // 1400
+    0x25B, 0x4DB, 0x9D5, 0x5D2, 0xDA5, 0xD4A, 0xA95, 0x54D, 0xAAD, 0x3AA,  // 1410
+    0xBD2, 0xBC4, 0xB89, 0xA95, 0x52D, 0x5AD, 0xB6A, 0x6D4, 0xDC9, 0xD92,  // 1420
+    0xAA6, 0x956, 0x2AE, 0x56D, 0x36A, 0xB55, 0xAAA, 0x94D, 0x49D, 0x95D,  // 1430
+    0x2BA, 0x5B5, 0x5AA, 0xD55, 0xA9A, 0x92E, 0x26E, 0x55D, 0xADA, 0x6D4,  // 1440
+    0x6A5, 0xB27, 0xA4D, 0x4AD, 0x56D, 0xB5A, 0x754, 0xF49, 0xE92, 0xD26,  // 1450
+    0xA56, 0x356, 0x6B5, 0xBAA, 0xB92, 0xB25, 0x68B, 0xA9B, 0x55A, 0xADA,  // 1460
+    0x5B4, 0xDA9, 0xB52, 0xA9A, 0x536, 0x276, 0x575, 0xAF2, 0x6D4, 0x6A9,  // 1470
+    0x555, 0x2AD, 0x4BD, 0x9BA, 0x574, 0xB69, 0xB52, 0xA95, 0x52D, 0xA5D,  // 1480
+    0x4DA, 0xAD9, 0x6B2, 0xE95, 0xE2A, 0xC96, 0x92E, 0xAAD, 0x56A, 0xD65,  // 1490
+    0xD4A, 0xD15, 0x62B, 0xC5B, 0x53A, 0x6B5, 0xDB2, 0xD64, 0xD29, 0xA55,  // 1500
+    0x4AD, 0x96D, 0xAEA, 0x6E8, 0xED1, 0xDA4, 0xD4A, 0xA6A, 0x2DA, 0x5B9,  // 1510
+    0xB72, 0xB68, 0x6D1, 0x655, 0x4AB, 0x95B, 0x2BA, 0x5B5, 0xDA9, 0xD52,  // 1520
+    0xCA6, 0x94E, 0x46E, 0x95D, 0x4DA, 0xAD5, 0xAAA, 0xA4D, 0x49B, 0x937,  // 1530
+    0x4B6, 0x975, 0xD6A, 0xD52, 0xAA5, 0x94B, 0x2AB, 0x55B, 0xAD9, 0x5D2,  // 1540
+    0xDC5, 0xD92, 0xB25, 0x555, 0xAB5, 0x5B4, 0xBA9, 0x7A2, 0x745, 0x593,  // 1550
+    0xAAB, 0x4D6, 0x9D6, 0x5D2, 0xBA5, 0xB4A, 0xA95, 0x4AD, 0x15D, 0x2DD,  // 1560
+    0x9DA, 0x5B4, 0x5A9, 0x52D, 0x25B, 0x8B7, 0x176, 0x56D, 0xB6A, 0xACA,  // 1570
+    0xA96, 0x52B, 0x15B, 0x2BB, 0x5B6, 0xDAA, 0xB94, 0xD46, 0xA8D, 0x52D,  // 1580
+    0xA9D, 0x55A, 0x755, 0x749, 0xF13, 0xE4A, 0xA96, 0x556, 0x6B5, 0xBAA,  // 1590
+    0xB94,  // 1600
+];
+
+/// Number of days of the month
+pub fn month_length(year: i16, month: i8) -> Result<i8, Error> {
+    if !(1..=12).contains(&month) {
+        return Err(Error::InvalidMonth(month));
+    }
+    let long_months = long_months(year)?;
+    Ok(29 + ((long_months >> (month - 1)) & 1) as i8)
+}
+
+/// Julian Day Number of the Umm al-Qura date
+pub fn umm_al_qura_to_julian(year: i16, month: i8, day: i8) -> Result<i32, Error> {
+    if !(1..=month_length(year, month)?).contains(&day) {
+        return Err(Error::InvalidArgument(format!(
+            "No such day in the Umm al-Qura calendar: {year}-{month}-{day}"
+        )));
+    }
+    let months_before = (1..month)
+        .map(|month| month_length(year, month).map(i32::from))
+        .sum::<Result<i32, Error>>()?;
+    Ok(year_start(year)? + months_before + i32::from(day) - 1)
+}
+
+/// Umm al-Qura date of the Julian Day Number, as (year, month, day)
+pub fn julian_to_umm_al_qura(julian_day: i32) -> Result<(i16, i8, i8), Error> {
+    if julian_day < FIRST_DAY {
+        return Err(out_of_range());
+    }
+    let mut year = FIRST_YEAR;
+    let mut start = FIRST_DAY;
+    while julian_day >= start + year_length(year)? {
+        start += year_length(year)?;
+        year += 1;
+    }
+    let mut month = 1;
+    while julian_day >= start + i32::from(month_length(year, month)?) {
+        start += i32::from(month_length(year, month)?);
+        month += 1;
+    }
+    Ok((year, month, (julian_day - start + 1) as i8))
+}
+
+/// Number of days of the year
+fn year_length(year: i16) -> Result<i32, Error> {
+    Ok(348 + long_months(year)?.count_ones() as i32)
+}
+
+/// Julian Day Number of 1 Muharram
+fn year_start(year: i16) -> Result<i32, Error> {
+    long_months(year)?;
+    let days = (FIRST_YEAR..year)
+        .map(year_length)
+        .sum::<Result<i32, Error>>()?;
+    Ok(FIRST_DAY + days)
+}
+
+/// Official calendar of Saudi Arabia, from 1300 to 1600 AH
+#[derive(PartialEq, Debug, Default, Copy, Clone)]
+pub struct UmmAlQura;
+
+impl HijriCalendar for UmmAlQura {
+    fn to_julian_day(&self, year: i16, month: i8, day: i8) -> Result<i32, Error> {
+        umm_al_qura_to_julian(year, month, day)
+    }
+    fn from_julian_day(&self, julian_day: i32) -> Result<(i16, i8, i8), Error> {
+        julian_to_umm_al_qura(julian_day)
+    }
+    fn month_length(&self, year: i16, month: i8) -> Result<i8, Error> {
+        month_length(year, month)
+    }
+    fn is_leap_year(&self, year: i16) -> Result<bool, Error> {
+        Ok(year_length(year)? == 355)
+    }
+}
+
+fn long_months(year: i16) -> Result<u16, Error> {
+    if !(FIRST_YEAR..=LAST_YEAR).contains(&year) {
+        return Err(out_of_range());
+    }
+    Ok(LONG_MONTHS[(year - FIRST_YEAR) as usize])
+}
+
+fn out_of_range() -> Error {
+    Error::InvalidArgument(format!(
+        "The Umm al-Qura calendar is only available from {FIRST_YEAR} to {LAST_YEAR} AH"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil;
+
+    use super::*;
+    use crate::hijri::cal::{gregorian_to_julian_day as julian_day, julian_to_gregorian};
+
+    #[test]
+    fn official_dates() -> Result<(), Error> {
+        // (Umm al-Qura, Gregorian)
+        let dates = [
+            ((1300, 1, 1), civil::date(1882, 11, 12)),
+            ((1441, 8, 25), civil::date(2020, 4, 18)),
+            ((1442, 8, 27), civil::date(2021, 4, 9)),
+            ((1446, 9, 1), civil::date(2025, 3, 1)),
+            ((1446, 10, 1), civil::date(2025, 3, 30)),
+            ((1600, 12, 30), civil::date(2174, 11, 25)),
+        ];
+        for ((year, month, day), gregorian) in dates {
+            let julian = umm_al_qura_to_julian(year, month, day)?;
+            assert_eq!(julian_to_gregorian(julian as f32), gregorian);
+            assert_eq!(
+                julian_to_umm_al_qura(julian_day(gregorian))?,
+                (year, month, day)
+            );
+        }
+        Ok(())
+    }
+    #[test]
+    fn month_lengths() -> Result<(), Error> {
+        assert_eq!(month_length(1446, 8)?, 29);
+        assert_eq!(month_length(1446, 9)?, 29);
+        assert_eq!(month_length(1600, 12)?, 30);
+        Ok(())
+    }
+    #[test]
+    fn round_trip() -> Result<(), Error> {
+        let first = umm_al_qura_to_julian(FIRST_YEAR, 1, 1)?;
+        let last = umm_al_qura_to_julian(LAST_YEAR, 12, month_length(LAST_YEAR, 12)?)?;
+        for julian in first..=last {
+            let (year, month, day) = julian_to_umm_al_qura(julian)?;
+            assert_eq!(umm_al_qura_to_julian(year, month, day)?, julian);
+        }
+        Ok(())
+    }
+    #[test]
+    fn out_of_range() {
+        assert!(umm_al_qura_to_julian(1299, 12, 29).is_err());
+        assert!(umm_al_qura_to_julian(1601, 1, 1).is_err());
+        assert!(umm_al_qura_to_julian(1446, 9, 30).is_err());
+        assert!(julian_to_umm_al_qura(2_408_761).is_err());
+        assert!(julian_to_umm_al_qura(julian_day(civil::date(2174, 11, 26))).is_err());
+    }
+}
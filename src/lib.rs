@@ -2,6 +2,8 @@
 
 pub mod error;
 pub mod hijri;
+pub mod moon;
+pub mod qibla;
 pub mod salah;
 mod time;
 
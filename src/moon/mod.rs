@@ -0,0 +1,7 @@
+mod phase;
+mod position;
+mod visibility;
+
+// shorter access for library consumer
+pub use phase::Phase;
+pub use visibility::{Odeh, Visibility, Yallop};
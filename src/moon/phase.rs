@@ -0,0 +1,413 @@
+//! Instants of the phases of the moon, from Astronomical Algorithms (Jean Meeus), chapter 49.
+//! The errors are under a minute, f64 is needed for the Julian Days.
+
+use jiff::{Timestamp, civil};
+
+/// Mean synodic month, in days
+const SYNODIC_MONTH: f64 = 29.530_588_861;
+
+/// Julian Ephemeris Day of the mean new moon of 6 Jan. 2000 (k = 0)
+const FIRST_NEW_MOON: f64 = 2_451_550.097_66;
+
+/// Julian Day of 1970-01-01 00:00 UTC
+const UNIX_EPOCH: f64 = 2_440_587.5;
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Phase {
+    /// Conjunction of the moon and the sun
+    NewMoon,
+    FirstQuarter,
+    FullMoon,
+    LastQuarter,
+}
+
+impl Phase {
+    /// Fraction of the lunation
+    const fn fraction(self) -> f64 {
+        match self {
+            Self::NewMoon => 0.0,
+            Self::FirstQuarter => 0.25,
+            Self::FullMoon => 0.5,
+            Self::LastQuarter => 0.75,
+        }
+    }
+    /// Instant of the phase nearest to the date (at noon UTC)
+    pub fn near(self, date: civil::Date) -> Result<Timestamp, crate::Error> {
+        let noon = date.at(12, 0, 0, 0).in_tz("UTC")?.timestamp();
+        let julian_day = julian_day(noon);
+        let mut nearest = self.instant(self.lunation(julian_day))?;
+        for k in [-1.0, 1.0].map(|shift| self.lunation(julian_day) + shift) {
+            let time = self.instant(k)?;
+            if time.duration_since(noon).abs() < nearest.duration_since(noon).abs() {
+                nearest = time;
+            }
+        }
+        Ok(nearest)
+    }
+    /// First instant of the phase after `time`
+    pub fn after(self, time: Timestamp) -> Result<Timestamp, crate::Error> {
+        let mut k = self.lunation(julian_day(time)) - 1.0;
+        loop {
+            let instant = self.instant(k)?;
+            if instant > time {
+                return Ok(instant);
+            }
+            k += 1.0;
+        }
+    }
+    /// Last instant of the phase before, or at, `time`
+    pub fn before(self, time: Timestamp) -> Result<Timestamp, crate::Error> {
+        let mut k = self.lunation(julian_day(time)) + 1.0;
+        loop {
+            let instant = self.instant(k)?;
+            if instant <= time {
+                return Ok(instant);
+            }
+            k -= 1.0;
+        }
+    }
+    /// Number of lunations since 6 Jan. 2000 (k) of the nearest phase
+    fn lunation(self, julian_day: f64) -> f64 {
+        ((julian_day - FIRST_NEW_MOON) / SYNODIC_MONTH - self.fraction()).round() + self.fraction()
+    }
+    /// Instant of the phase of the lunation `k`
+    fn instant(self, k: f64) -> Result<Timestamp, crate::Error> {
+        let julian_ephemeris_day = self.julian_ephemeris_day(k);
+        let year = 2000.0 + (julian_ephemeris_day - 2_451_545.0) / 365.25;
+        let julian_day = julian_ephemeris_day - delta_t(year) / 86400.0;
+        let seconds = ((julian_day - UNIX_EPOCH) * 86400.0).round() as i64;
+        Ok(Timestamp::from_second(seconds)?)
+    }
+    /// Julian Ephemeris Day (TT) of the phase of the lunation `k`
+    fn julian_ephemeris_day(self, k: f64) -> f64 {
+        let t = k / 1236.85;
+        let mean = 0.000_000_000_73_f64.mul_add(
+            t.powi(4),
+            (-0.000_000_150_f64).mul_add(
+                t.powi(3),
+                0.000_154_37_f64.mul_add(t.powi(2), SYNODIC_MONTH.mul_add(k, FIRST_NEW_MOON)),
+            ),
+        );
+
+        // eccentricity of the earth's orbit
+        let e = (-0.000_007_4_f64).mul_add(t.powi(2), (-0.002_516_f64).mul_add(t, 1.0));
+        // sun's mean anomaly
+        let m = (-0.000_000_11_f64).mul_add(
+            t.powi(3),
+            (-0.000_001_4_f64).mul_add(t.powi(2), 29.105_356_70_f64.mul_add(k, 2.5534)),
+        );
+        // moon's mean anomaly
+        let mp = (-0.000_000_058_f64).mul_add(
+            t.powi(4),
+            0.000_012_38_f64.mul_add(
+                t.powi(3),
+                0.010_758_2_f64.mul_add(t.powi(2), 385.816_935_28_f64.mul_add(k, 201.5643)),
+            ),
+        );
+        // moon's argument of latitude
+        let f = 0.000_000_011_f64.mul_add(
+            t.powi(4),
+            (-0.000_002_27_f64).mul_add(
+                t.powi(3),
+                (-0.001_611_8_f64).mul_add(t.powi(2), 390.670_502_84_f64.mul_add(k, 160.7108)),
+            ),
+        );
+        // longitude of the ascending node of the lunar orbit
+        let omega = 0.000_002_15_f64.mul_add(
+            t.powi(3),
+            0.002_067_2_f64.mul_add(t.powi(2), (-1.563_755_88_f64).mul_add(k, 124.7746)),
+        );
+
+        let correction = match self {
+            Self::NewMoon => new_or_full_moon(
+                [
+                    0.40720, 0.17241, 0.01608, 0.01039, 0.00739, 0.00514, 0.00208,
+                ],
+                e,
+                m,
+                mp,
+                f,
+                omega,
+            ),
+            Self::FullMoon => new_or_full_moon(
+                [
+                    0.40614, 0.17302, 0.01614, 0.01043, 0.00734, 0.00515, 0.00209,
+                ],
+                e,
+                m,
+                mp,
+                f,
+                omega,
+            ),
+            Self::FirstQuarter => quarter(e, m, mp, f, omega) + quarter_shift(e, m, mp, f),
+            Self::LastQuarter => quarter(e, m, mp, f, omega) - quarter_shift(e, m, mp, f),
+        };
+        mean + correction + planetary_correction(k, t)
+    }
+}
+
+/// Sum of `coefficient * sin(angle)`, the angles are in degrees
+fn sum(terms: &[(f64, f64)]) -> f64 {
+    terms
+        .iter()
+        .map(|(coefficient, angle)| coefficient * angle.to_radians().sin())
+        .sum()
+}
+
+/// Periodic terms of the new and full moons, only the 7 first coefficients differ
+fn new_or_full_moon(first: [f64; 7], e: f64, m: f64, mp: f64, f: f64, omega: f64) -> f64 {
+    sum(&[
+        (-first[0], mp),
+        (first[1] * e, m),
+        (first[2], 2.0 * mp),
+        (first[3], 2.0 * f),
+        (first[4] * e, mp - m),
+        (-first[5] * e, mp + m),
+        (first[6] * e * e, 2.0 * m),
+        (-0.00111, mp - 2.0 * f),
+        (-0.00057, mp + 2.0 * f),
+        (0.00056 * e, 2.0 * mp + m),
+        (-0.00042, 3.0 * mp),
+        (0.00042 * e, m + 2.0 * f),
+        (0.00038 * e, m - 2.0 * f),
+        (-0.00024 * e, 2.0 * mp - m),
+        (-0.00017, omega),
+        (-0.00007, mp + 2.0 * m),
+        (0.00004, 2.0 * mp - 2.0 * f),
+        (0.00004, 3.0 * m),
+        (0.00003, mp + m - 2.0 * f),
+        (0.00003, 2.0 * mp + 2.0 * f),
+        (-0.00003, mp + m + 2.0 * f),
+        (0.00003, mp - m + 2.0 * f),
+        (-0.00002, mp - m - 2.0 * f),
+        (-0.00002, 3.0 * mp + m),
+        (0.00002, 4.0 * mp),
+    ])
+}
+
+/// Periodic terms of the first and last quarters
+fn quarter(e: f64, m: f64, mp: f64, f: f64, omega: f64) -> f64 {
+    sum(&[
+        (-0.62801, mp),
+        (0.17172 * e, m),
+        (-0.01183 * e, mp + m),
+        (0.00862, 2.0 * mp),
+        (0.00804, 2.0 * f),
+        (0.00454 * e, mp - m),
+        (0.00204 * e * e, 2.0 * m),
+        (-0.00180, mp - 2.0 * f),
+        (-0.00070, mp + 2.0 * f),
+        (-0.00040, 3.0 * mp),
+        (-0.00034 * e, 2.0 * mp - m),
+        (0.00032 * e, m + 2.0 * f),
+        (0.00032 * e, m - 2.0 * f),
+        (-0.00028 * e * e, mp + 2.0 * m),
+        (0.00027 * e, 2.0 * mp + m),
+        (-0.00017, omega),
+        (-0.00005, mp - m - 2.0 * f),
+        (0.00004, 2.0 * mp + 2.0 * f),
+        (-0.00004, mp + m + 2.0 * f),
+        (0.00004, mp - 2.0 * m),
+        (0.00003, mp + m - 2.0 * f),
+        (0.00003, 3.0 * m),
+        (0.00002, 2.0 * mp - 2.0 * f),
+        (0.00002, mp - m + 2.0 * f),
+        (-0.00002, 3.0 * mp + m),
+    ])
+}
+
+/// W, added to the first quarter and subtracted from the last one
+fn quarter_shift(e: f64, m: f64, mp: f64, f: f64) -> f64 {
+    let cos = |angle: f64| angle.to_radians().cos();
+    0.00306 - 0.00038 * e * cos(m) + 0.00026 * cos(mp) - 0.00002 * cos(mp - m)
+        + 0.00002 * cos(mp + m)
+        + 0.00002 * cos(2.0 * f)
+}
+
+/// Additional corrections for all the phases, from the planetary arguments
+fn planetary_correction(k: f64, t: f64) -> f64 {
+    sum(&[
+        (
+            0.000_325,
+            (-0.009_173 * t).mul_add(t, 0.107_408_f64.mul_add(k, 299.77)),
+        ),
+        (0.000_165, 0.016_321_f64.mul_add(k, 251.88)),
+        (0.000_164, 26.651_886_f64.mul_add(k, 251.83)),
+        (0.000_126, 36.412_478_f64.mul_add(k, 349.42)),
+        (0.000_110, 18.206_239_f64.mul_add(k, 84.66)),
+        (0.000_062, 53.303_771_f64.mul_add(k, 141.74)),
+        (0.000_060, 2.453_732_f64.mul_add(k, 207.14)),
+        (0.000_056, 7.306_860_f64.mul_add(k, 154.84)),
+        (0.000_047, 27.261_239_f64.mul_add(k, 34.52)),
+        (0.000_042, 0.121_824_f64.mul_add(k, 207.19)),
+        (0.000_040, 1.844_379_f64.mul_add(k, 291.34)),
+        (0.000_037, 24.198_154_f64.mul_add(k, 161.72)),
+        (0.000_035, 25.513_099_f64.mul_add(k, 239.56)),
+        (0.000_023, 3.592_518_f64.mul_add(k, 331.55)),
+    ])
+}
+
+/// Julian Day (UT) of the instant
+pub(crate) fn julian_day(time: Timestamp) -> f64 {
+    time.as_second() as f64 / 86400.0 + UNIX_EPOCH
+}
+
+/// ΔT = TT - UT in seconds, polynomial expressions of Espenak and Meeus (NASA, 2006)
+pub(crate) fn delta_t(year: f64) -> f64 {
+    let polynomial = |t: f64, coefficients: &[f64]| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0.0_f64, |sum, coefficient| sum.mul_add(t, *coefficient))
+    };
+    // long-term parabola
+    let parabola = |year: f64| 32.0_f64.mul_add(((year - 1820.0) / 100.0).powi(2), -20.0);
+    match year {
+        y if y < -500.0 => parabola(y),
+        y if y < 500.0 => polynomial(
+            y / 100.0,
+            &[
+                10583.6,
+                -1014.41,
+                33.783_11,
+                -5.952_053,
+                -0.179_845_2,
+                0.022_174_192,
+                0.009_031_652_1,
+            ],
+        ),
+        y if y < 1600.0 => polynomial(
+            (y - 1000.0) / 100.0,
+            &[
+                1574.2,
+                -556.01,
+                71.234_72,
+                0.319_781,
+                -0.850_346_3,
+                -0.005_050_998,
+                0.008_357_207_3,
+            ],
+        ),
+        y if y < 1700.0 => polynomial(y - 1600.0, &[120.0, -0.9808, -0.01532, 1.0 / 7129.0]),
+        y if y < 1800.0 => polynomial(
+            y - 1700.0,
+            &[8.83, 0.1603, -0.005_928_5, 0.000_133_36, -1.0 / 1_174_000.0],
+        ),
+        y if y < 1860.0 => polynomial(
+            y - 1800.0,
+            &[
+                13.72,
+                -0.332_447,
+                0.006_861_2,
+                0.004_111_6,
+                -0.000_374_36,
+                0.000_012_127_2,
+                -0.000_000_169_9,
+                0.000_000_000_875,
+            ],
+        ),
+        y if y < 1900.0 => polynomial(
+            y - 1860.0,
+            &[
+                7.62,
+                0.5737,
+                -0.251_754,
+                0.016_806_68,
+                -0.000_447_362_4,
+                1.0 / 233_174.0,
+            ],
+        ),
+        y if y < 1920.0 => polynomial(
+            y - 1900.0,
+            &[-2.79, 1.494_119, -0.059_893_9, 0.006_196_6, -0.000_197],
+        ),
+        y if y < 1941.0 => polynomial(y - 1920.0, &[21.20, 0.84493, -0.076_100, 0.002_093_6]),
+        y if y < 1961.0 => polynomial(y - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0]),
+        y if y < 1986.0 => polynomial(y - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0]),
+        y if y < 2005.0 => polynomial(
+            y - 2000.0,
+            &[
+                63.86,
+                0.3345,
+                -0.060_374,
+                0.001_727_5,
+                0.000_651_814,
+                0.000_023_735_99,
+            ],
+        ),
+        y if y < 2050.0 => polynomial(y - 2000.0, &[62.92, 0.32217, 0.005_589]),
+        y if y < 2150.0 => parabola(y) - 0.5628 * (2150.0 - y),
+        y => parabola(y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::SignedDuration;
+
+    use super::*;
+
+    /// Within a minute of the published time
+    fn assert_near(time: Timestamp, expected: &str) -> Result<(), crate::Error> {
+        let error = time.duration_since(expected.parse()?).abs();
+        assert!(error < SignedDuration::from_mins(1), "{time} != {expected}");
+        Ok(())
+    }
+
+    #[test]
+    fn meeus_examples() {
+        // Example 49.a, new moon of February 1977
+        let jde = Phase::NewMoon.julian_ephemeris_day(-283.0);
+        assert!((jde - 2_443_192.651_18).abs() < 0.000_01);
+        // Example 49.b, first last quarter of 2044
+        let jde = Phase::LastQuarter.julian_ephemeris_day(544.75);
+        assert!((jde - 2_467_636.491_86).abs() < 0.000_01);
+    }
+    #[test]
+    fn new_moon_of_1977() -> Result<(), crate::Error> {
+        // 3h37m42s TD, with ΔT = 48s
+        let time = Phase::NewMoon.near(civil::date(1977, 2, 15))?;
+        assert_near(time, "1977-02-18T03:36:54Z")
+    }
+    #[test]
+    fn phases_of_march_2025() -> Result<(), crate::Error> {
+        let phases = [
+            (Phase::FirstQuarter, "2025-03-06T16:32:00Z"),
+            (Phase::FullMoon, "2025-03-14T06:55:00Z"),
+            (Phase::LastQuarter, "2025-03-22T11:29:00Z"),
+            (Phase::NewMoon, "2025-03-29T10:58:00Z"),
+        ];
+        for (phase, expected) in phases {
+            assert_near(phase.near(civil::date(2025, 3, 17))?, expected)?;
+        }
+        Ok(())
+    }
+    #[test]
+    fn after_and_before() -> Result<(), crate::Error> {
+        let new_moon = "2025-03-29T10:58:00Z";
+        let time = Phase::NewMoon.after("2025-03-01T00:00:00Z".parse()?)?;
+        assert_near(time, new_moon)?;
+
+        let time = Phase::NewMoon.before("2025-04-20T00:00:00Z".parse()?)?;
+        assert_near(time, new_moon)?;
+
+        let next = Phase::NewMoon.after(time)?;
+        assert_near(next, "2025-04-27T19:31:00Z")?;
+        Ok(())
+    }
+    #[test]
+    fn delta_t_values() {
+        // observed: 0.0 s in 1900, 63.8 s in 2000
+        assert!(delta_t(1900.0).abs() < 3.0);
+        assert!((delta_t(2000.0) - 63.8).abs() < 0.1);
+        // continuous at the bounds
+        for year in [
+            1600.0, 1700.0, 1800.0, 1900.0, 1941.0, 1986.0, 2005.0, 2050.0, 2150.0,
+        ] {
+            assert!(
+                (delta_t(year - 0.001) - delta_t(year)).abs() < 1.0,
+                "{year}"
+            );
+        }
+    }
+}
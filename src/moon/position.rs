@@ -0,0 +1,377 @@
+//! Positions of the sun and the moon, from Astronomical Algorithms (Jean Meeus), chapters 12, 13, 25 and 47.
+//! Angles are in degrees and Julian Days in f64.
+
+use jiff::Timestamp;
+
+use crate::moon::phase::{delta_t, julian_day};
+use crate::salah::Location;
+
+/// Equatorial radius of the earth, in km
+const EARTH_RADIUS: f64 = 6378.14;
+
+/// Periodic terms of the moon's longitude (Σl) and distance (Σr): D, M, M', F, sine and cosine coefficients
+#[rustfmt::skip]
+const LONGITUDE_TERMS: [(i8, i8, i8, i8, f64, f64); 60] = [
+    (0, 0, 1, 0, 6_288_774.0, -20_905_355.0),
+    (2, 0, -1, 0, 1_274_027.0, -3_699_111.0),
+    (2, 0, 0, 0, 658_314.0, -2_955_968.0),
+    (0, 0, 2, 0, 213_618.0, -569_925.0),
+    (0, 1, 0, 0, -185_116.0, 48_888.0),
+    (0, 0, 0, 2, -114_332.0, -3_149.0),
+    (2, 0, -2, 0, 58_793.0, 246_158.0),
+    (2, -1, -1, 0, 57_066.0, -152_138.0),
+    (2, 0, 1, 0, 53_322.0, -170_733.0),
+    (2, -1, 0, 0, 45_758.0, -204_586.0),
+    (0, 1, -1, 0, -40_923.0, -129_620.0),
+    (1, 0, 0, 0, -34_720.0, 108_743.0),
+    (0, 1, 1, 0, -30_383.0, 104_755.0),
+    (2, 0, 0, -2, 15_327.0, 10_321.0),
+    (0, 0, 1, 2, -12_528.0, 0.0),
+    (0, 0, 1, -2, 10_980.0, 79_661.0),
+    (4, 0, -1, 0, 10_675.0, -34_782.0),
+    (0, 0, 3, 0, 10_034.0, -23_210.0),
+    (4, 0, -2, 0, 8_548.0, -21_636.0),
+    (2, 1, -1, 0, -7_888.0, 24_208.0),
+    (2, 1, 0, 0, -6_766.0, 30_824.0),
+    (1, 0, -1, 0, -5_163.0, -8_379.0),
+    (1, 1, 0, 0, 4_987.0, -16_675.0),
+    (2, -1, 1, 0, 4_036.0, -12_831.0),
+    (2, 0, 2, 0, 3_994.0, -10_445.0),
+    (4, 0, 0, 0, 3_861.0, -11_650.0),
+    (2, 0, -3, 0, 3_665.0, 14_403.0),
+    (0, 1, -2, 0, -2_689.0, -7_003.0),
+    (2, 0, -1, 2, -2_602.0, 0.0),
+    (2, -1, -2, 0, 2_390.0, 10_056.0),
+    (1, 0, 1, 0, -2_348.0, 6_322.0),
+    (2, -2, 0, 0, 2_236.0, -9_884.0),
+    (0, 1, 2, 0, -2_120.0, 5_751.0),
+    (0, 2, 0, 0, -2_069.0, 0.0),
+    (2, -2, -1, 0, 2_048.0, -4_950.0),
+    (2, 0, 1, -2, -1_773.0, 4_130.0),
+    (2, 0, 0, 2, -1_595.0, 0.0),
+    (4, -1, -1, 0, 1_215.0, -3_958.0),
+    (0, 0, 2, 2, -1_110.0, 0.0),
+    (3, 0, -1, 0, -892.0, 3_258.0),
+    (2, 1, 1, 0, -810.0, 2_616.0),
+    (4, -1, -2, 0, 759.0, -1_897.0),
+    (0, 2, -1, 0, -713.0, -2_117.0),
+    (2, 2, -1, 0, -700.0, 2_354.0),
+    (2, 1, -2, 0, 691.0, 0.0),
+    (2, -1, 0, -2, 596.0, 0.0),
+    (4, 0, 1, 0, 549.0, -1_423.0),
+    (0, 0, 4, 0, 537.0, -1_117.0),
+    (4, -1, 0, 0, 520.0, -1_571.0),
+    (1, 0, -2, 0, -487.0, -1_739.0),
+    (2, 1, 0, -2, -399.0, 0.0),
+    (0, 0, 2, -2, -381.0, -4_421.0),
+    (1, 1, 1, 0, 351.0, 0.0),
+    (3, 0, -2, 0, -340.0, 0.0),
+    (4, 0, -3, 0, 330.0, 0.0),
+    (2, -1, 2, 0, 327.0, 0.0),
+    (0, 2, 1, 0, -323.0, 1_165.0),
+    (1, 1, -1, 0, 299.0, 0.0),
+    (2, 0, 3, 0, 294.0, 0.0),
+    (2, 0, -1, -2, 0.0, 8_752.0),
+];
+
+/// Periodic terms of the moon's latitude (Σb): D, M, M', F and sine coefficient
+#[rustfmt::skip]
+const LATITUDE_TERMS: [(i8, i8, i8, i8, f64); 60] = [
+    (0, 0, 0, 1, 5_128_122.0),
+    (0, 0, 1, 1, 280_602.0),
+    (0, 0, 1, -1, 277_693.0),
+    (2, 0, 0, -1, 173_237.0),
+    (2, 0, -1, 1, 55_413.0),
+    (2, 0, -1, -1, 46_271.0),
+    (2, 0, 0, 1, 32_573.0),
+    (0, 0, 2, 1, 17_198.0),
+    (2, 0, 1, -1, 9_266.0),
+    (0, 0, 2, -1, 8_822.0),
+    (2, -1, 0, -1, 8_216.0),
+    (2, 0, -2, -1, 4_324.0),
+    (2, 0, 1, 1, 4_200.0),
+    (2, 1, 0, -1, -3_359.0),
+    (2, -1, -1, 1, 2_463.0),
+    (2, -1, 0, 1, 2_211.0),
+    (2, -1, -1, -1, 2_065.0),
+    (0, 1, -1, -1, -1_870.0),
+    (4, 0, -1, -1, 1_828.0),
+    (0, 1, 0, 1, -1_794.0),
+    (0, 0, 0, 3, -1_749.0),
+    (0, 1, -1, 1, -1_565.0),
+    (1, 0, 0, 1, -1_491.0),
+    (0, 1, 1, 1, -1_475.0),
+    (0, 1, 1, -1, -1_410.0),
+    (0, 1, 0, -1, -1_344.0),
+    (1, 0, 0, -1, -1_335.0),
+    (0, 0, 3, 1, 1_107.0),
+    (4, 0, 0, -1, 1_021.0),
+    (4, 0, -1, 1, 833.0),
+    (0, 0, 1, -3, 777.0),
+    (4, 0, -2, 1, 671.0),
+    (2, 0, 0, -3, 607.0),
+    (2, 0, 2, -1, 596.0),
+    (2, -1, 1, -1, 491.0),
+    (2, 0, -2, 1, -451.0),
+    (0, 0, 3, -1, 439.0),
+    (2, 0, 2, 1, 422.0),
+    (2, 0, -3, -1, 421.0),
+    (2, 1, -1, 1, -366.0),
+    (2, 1, 0, 1, -351.0),
+    (4, 0, 0, 1, 331.0),
+    (2, -1, 1, 1, 315.0),
+    (2, -2, 0, -1, 302.0),
+    (0, 0, 1, 3, -283.0),
+    (2, 1, 1, -1, -229.0),
+    (1, 1, 0, -1, 223.0),
+    (1, 1, 0, 1, 223.0),
+    (0, 1, -2, -1, -220.0),
+    (2, 1, -1, -1, -220.0),
+    (1, 0, 1, 1, -185.0),
+    (2, -1, -2, -1, 181.0),
+    (0, 1, 2, 1, -177.0),
+    (4, 0, -2, -1, 176.0),
+    (4, -1, -1, -1, 166.0),
+    (1, 0, 1, -1, -164.0),
+    (4, 0, 1, -1, 132.0),
+    (1, 0, -1, -1, -119.0),
+    (4, -1, 0, -1, 115.0),
+    (2, -2, 0, 1, 107.0),
+];
+
+/// Geocentric position, on the ecliptic of the date
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub(crate) struct Ecliptic {
+    pub longitude: f64,
+    pub latitude: f64,
+    /// in km
+    pub distance: f64,
+}
+
+/// Position seen from a location, without refraction
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub(crate) struct Horizontal {
+    pub altitude: f64,
+    /// clockwise from true north
+    pub azimuth: f64,
+}
+
+fn sin(angle: f64) -> f64 {
+    angle.to_radians().sin()
+}
+
+fn cos(angle: f64) -> f64 {
+    angle.to_radians().cos()
+}
+
+/// Julian centuries since J2000.0
+fn centuries(julian_ephemeris_day: f64) -> f64 {
+    (julian_ephemeris_day - 2_451_545.0) / 36525.0
+}
+
+/// Julian Ephemeris Day (TT) of the instant
+pub(crate) fn julian_ephemeris_day(time: Timestamp) -> f64 {
+    let julian_day = julian_day(time);
+    let year = 2000.0 + (julian_day - 2_451_545.0) / 365.25;
+    julian_day + delta_t(year) / 86400.0
+}
+
+/// Apparent position of the sun (chapter 25, low accuracy), about 0.01°
+pub(crate) fn sun(julian_ephemeris_day: f64) -> Ecliptic {
+    let t = centuries(julian_ephemeris_day);
+    let mean_longitude = 0.000_303_2_f64.mul_add(t * t, 36_000.769_83_f64.mul_add(t, 280.466_46));
+    let mean_anomaly = (-0.000_153_7_f64).mul_add(t * t, 35_999.050_29_f64.mul_add(t, 357.529_11));
+    let eccentricity =
+        (-0.000_000_126_7_f64).mul_add(t * t, (-0.000_042_037_f64).mul_add(t, 0.016_708_634));
+    let center = (-0.000_014_f64).mul_add(t * t, (-0.004_817_f64).mul_add(t, 1.914_602))
+        * sin(mean_anomaly)
+        + (-0.000_101_f64).mul_add(t, 0.019_993) * sin(2.0 * mean_anomaly)
+        + 0.000_289 * sin(3.0 * mean_anomaly);
+    let true_anomaly = mean_anomaly + center;
+    let radius = 1.000_001_018 * eccentricity.mul_add(-eccentricity, 1.0)
+        / eccentricity.mul_add(cos(true_anomaly), 1.0);
+
+    // nutation and aberration
+    let omega = (-1934.136_f64).mul_add(t, 125.04);
+    let longitude = mean_longitude + center - 0.005_69 - 0.004_78 * sin(omega);
+    Ecliptic {
+        longitude: longitude.rem_euclid(360.0),
+        latitude: 0.0,
+        distance: radius * 149_597_870.7,
+    }
+}
+
+/// Position of the moon (chapter 47), about 10" in longitude and 4" in latitude
+pub(crate) fn moon(julian_ephemeris_day: f64) -> Ecliptic {
+    let t = centuries(julian_ephemeris_day);
+    let polynomial = |coefficients: [f64; 5]| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0.0_f64, |sum, coefficient| sum.mul_add(t, *coefficient))
+    };
+    // mean longitude
+    let lp = polynomial([
+        218.316_447_7,
+        481_267.881_234_21,
+        -0.001_578_6,
+        1.0 / 538_841.0,
+        -1.0 / 65_194_000.0,
+    ]);
+    // mean elongation
+    let d = polynomial([
+        297.850_192_1,
+        445_267.111_403_4,
+        -0.001_881_9,
+        1.0 / 545_868.0,
+        -1.0 / 113_065_000.0,
+    ]);
+    // sun's mean anomaly
+    let m = polynomial([
+        357.529_109_2,
+        35_999.050_290_9,
+        -0.000_153_6,
+        1.0 / 24_490_000.0,
+        0.0,
+    ]);
+    // moon's mean anomaly
+    let mp = polynomial([
+        134.963_396_4,
+        477_198.867_505_5,
+        0.008_741_4,
+        1.0 / 69_699.0,
+        -1.0 / 14_712_000.0,
+    ]);
+    // argument of latitude
+    let f = polynomial([
+        93.272_095_0,
+        483_202.017_523_3,
+        -0.003_653_9,
+        -1.0 / 3_526_000.0,
+        1.0 / 863_310_000.0,
+    ]);
+
+    let a1 = 131.849_f64.mul_add(t, 119.75);
+    let a2 = 479_264.290_f64.mul_add(t, 53.09);
+    let a3 = 481_266.484_f64.mul_add(t, 313.45);
+    // eccentricity of the earth's orbit, for the terms with M
+    let e = (-0.000_007_4_f64).mul_add(t * t, (-0.002_516_f64).mul_add(t, 1.0));
+    let argument = |terms: (i8, i8, i8, i8)| {
+        let (dc, mc, mpc, fc) = terms;
+        let factor = e.powi(i32::from(mc.abs()));
+        let angle = f64::from(dc) * d + f64::from(mc) * m + f64::from(mpc) * mp + f64::from(fc) * f;
+        (factor, angle)
+    };
+
+    let (mut sum_l, mut sum_r) = (0.0, 0.0);
+    for (dc, mc, mpc, fc, l, r) in LONGITUDE_TERMS {
+        let (factor, angle) = argument((dc, mc, mpc, fc));
+        sum_l += factor * l * sin(angle);
+        sum_r += factor * r * cos(angle);
+    }
+    let mut sum_b = 0.0;
+    for (dc, mc, mpc, fc, b) in LATITUDE_TERMS {
+        let (factor, angle) = argument((dc, mc, mpc, fc));
+        sum_b += factor * b * sin(angle);
+    }
+    sum_l += 3958.0 * sin(a1) + 1962.0 * sin(lp - f) + 318.0 * sin(a2);
+    sum_b += -2235.0 * sin(lp)
+        + 382.0 * sin(a3)
+        + 175.0 * sin(a1 - f)
+        + 175.0 * sin(a1 + f)
+        + 127.0 * sin(lp - mp)
+        - 115.0 * sin(lp + mp);
+
+    Ecliptic {
+        longitude: (lp + sum_l / 1_000_000.0).rem_euclid(360.0),
+        latitude: sum_b / 1_000_000.0,
+        distance: 385_000.56 + sum_r / 1000.0,
+    }
+}
+
+/// Right ascension and declination (chapter 13), with the mean obliquity of the ecliptic (chapter 22)
+pub(crate) fn equatorial(position: Ecliptic, julian_ephemeris_day: f64) -> (f64, f64) {
+    let t = centuries(julian_ephemeris_day);
+    let obliquity =
+        23.439_291_1 - (46.815_0 * t + 0.000_59 * t * t - 0.001_813 * t.powi(3)) / 3600.0;
+    let (longitude, latitude) = (position.longitude, position.latitude);
+
+    let right_ascension = (sin(longitude) * cos(obliquity)
+        - latitude.to_radians().tan() * sin(obliquity))
+    .atan2(cos(longitude))
+    .to_degrees()
+    .rem_euclid(360.0);
+    let declination = (sin(latitude) * cos(obliquity)
+        + cos(latitude) * sin(obliquity) * sin(longitude))
+    .asin()
+    .to_degrees();
+    (right_ascension, declination)
+}
+
+/// Mean sidereal time at Greenwich (chapter 12)
+fn sidereal_time(julian_day: f64) -> f64 {
+    let t = centuries(julian_day);
+    (280.460_618_37 + 360.985_647_366_29 * (julian_day - 2_451_545.0) + 0.000_387_933 * t * t
+        - t.powi(3) / 38_710_000.0)
+        .rem_euclid(360.0)
+}
+
+/// Geocentric altitude and azimuth of the position at the instant
+pub(crate) fn horizontal(position: Ecliptic, time: Timestamp, location: Location) -> Horizontal {
+    let (right_ascension, declination) = equatorial(position, julian_ephemeris_day(time));
+    let latitude = f64::from(location.latitude());
+    let hour_angle =
+        sidereal_time(julian_day(time)) + f64::from(location.longitude()) - right_ascension;
+
+    let altitude = (sin(latitude) * sin(declination)
+        + cos(latitude) * cos(declination) * cos(hour_angle))
+    .asin()
+    .to_degrees();
+    let azimuth = (-cos(declination) * sin(hour_angle))
+        .atan2(
+            sin(declination) * cos(latitude) - cos(declination) * cos(hour_angle) * sin(latitude),
+        )
+        .to_degrees()
+        .rem_euclid(360.0);
+    Horizontal { altitude, azimuth }
+}
+
+/// Equatorial horizontal parallax
+pub(crate) fn parallax(position: Ecliptic) -> f64 {
+    (EARTH_RADIUS / position.distance).asin().to_degrees()
+}
+
+/// Angular distance between two positions on the sky, from their altitudes and azimuths
+pub(crate) fn separation(first: Horizontal, second: Horizontal) -> f64 {
+    (sin(first.altitude) * sin(second.altitude)
+        + cos(first.altitude) * cos(second.altitude) * cos(first.azimuth - second.azimuth))
+    .clamp(-1.0, 1.0)
+    .acos()
+    .to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sun_of_meeus() {
+        // Example 25.a, 1992 October 13 at 0h TD
+        let sun = sun(2_448_908.5);
+        assert!((sun.longitude - 199.908_95).abs() < 0.000_1);
+        assert!((sun.distance / 149_597_870.7 - 0.997_66).abs() < 0.000_01);
+    }
+    #[test]
+    fn moon_of_meeus() {
+        // Example 47.a, 1992 April 12 at 0h TD
+        let moon = moon(2_448_724.5);
+        assert!((moon.longitude - 133.162_655).abs() < 0.000_01);
+        assert!((moon.latitude + 3.229_126).abs() < 0.000_01);
+        assert!((moon.distance - 368_409.7).abs() < 0.1);
+
+        // with the mean obliquity, the apparent position is α = 134.688470, δ = 13.768368
+        let (right_ascension, declination) = equatorial(moon, 2_448_724.5);
+        assert!((right_ascension - 134.688_470).abs() < 0.01);
+        assert!((declination - 13.768_368).abs() < 0.01);
+    }
+}
@@ -0,0 +1,203 @@
+//! Visibility of the new crescent at sunset, the instant of Maghreb.
+//! The altitudes are without refraction, from the centers of the sun and the moon.
+
+use jiff::{SignedDuration, Timestamp, civil, tz::TimeZone};
+
+use crate::moon::{
+    Phase,
+    position::{self, Horizontal},
+};
+use crate::salah::{Config, Location, PrayerTimes};
+
+/// Yallop's q-test (NAO Technical Note 69, 1997)
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Yallop {
+    /// Easily visible
+    A,
+    /// Visible under perfect conditions
+    B,
+    /// May need optical aid to find the crescent
+    C,
+    /// Will need optical aid to find the crescent
+    D,
+    /// Not visible with a telescope
+    E,
+    /// Not visible, below the Danjon limit
+    F,
+}
+
+/// Odeh's criterion (Experimental Astronomy, 2004)
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Odeh {
+    /// Visible by naked eye
+    NakedEye,
+    /// Visible by optical aid, could be seen by naked eye
+    OpticalAidOrNakedEye,
+    /// Visible by optical aid only
+    OpticalAid,
+    /// Not visible even by optical aid
+    NotVisible,
+}
+
+/// Position of the moon at sunset, the day of the nearest conjunction
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Visibility {
+    /// Maghreb, without any offset
+    pub sunset: Timestamp,
+    /// Nearest new moon
+    pub conjunction: Timestamp,
+    /// Time since the conjunction, negative before it
+    pub age: SignedDuration,
+    /// Topocentric altitude of the moon, in degrees
+    pub moon_altitude: f64,
+    /// Geocentric elongation of the moon from the sun (ARCL), in degrees
+    pub elongation: f64,
+    /// Geocentric difference of altitude between the moon and the sun (ARCV), in degrees
+    pub arc_of_vision: f64,
+    /// Topocentric width of the crescent, in arcminutes
+    pub width: f64,
+    /// Topocentric ARCV, for Odeh
+    topocentric_arc_of_vision: f64,
+    /// Width from the topocentric elongation, for Odeh
+    topocentric_width: f64,
+}
+
+impl Visibility {
+    /// Position of the moon at sunset of the date
+    pub fn new(location: Location, date: civil::Date) -> Result<Self, crate::Error> {
+        let hour = PrayerTimes::maghreb(date.at(0, 0, 0, 0), location, Config::new())?;
+        if !hour.is_finite() {
+            return Err(crate::Error::InvalidTime);
+        }
+        let midnight = date.to_zoned(TimeZone::UTC)?.timestamp();
+        let sunset = Timestamp::from_second(midnight.as_second() + (hour * 3600.0).round() as i64)?;
+        let conjunction = Phase::NewMoon.near(date)?;
+
+        let julian_ephemeris_day = position::julian_ephemeris_day(sunset);
+        let moon = position::moon(julian_ephemeris_day);
+        let sun = position::horizontal(position::sun(julian_ephemeris_day), sunset, location);
+        let geocentric = position::horizontal(moon, sunset, location);
+        let parallax = position::parallax(moon);
+        let topocentric = Horizontal {
+            altitude: geocentric.altitude - parallax * geocentric.altitude.to_radians().cos(),
+            ..geocentric
+        };
+
+        let elongation = position::separation(sun, geocentric);
+        let topocentric_elongation = position::separation(sun, topocentric);
+        // semi-diameter (arcminutes), increased by the nearness of the moon above the horizon
+        let semi_diameter = 0.272_45
+            * parallax
+            * 60.0
+            * (1.0 + geocentric.altitude.to_radians().sin() * parallax.to_radians().sin());
+        let width = |elongation: f64| semi_diameter * (1.0 - elongation.to_radians().cos());
+
+        Ok(Self {
+            sunset,
+            conjunction,
+            age: sunset.duration_since(conjunction),
+            moon_altitude: topocentric.altitude,
+            elongation,
+            arc_of_vision: geocentric.altitude - sun.altitude,
+            width: width(elongation),
+            topocentric_arc_of_vision: topocentric.altitude - sun.altitude,
+            topocentric_width: width(topocentric_elongation),
+        })
+    }
+    /// q value of Yallop, evaluated at sunset instead of the best time
+    pub fn yallop_q(&self) -> f64 {
+        (self.arc_of_vision - minimum_arc_of_vision(self.width, 11.8371)) / 10.0
+    }
+    pub fn yallop(&self) -> Yallop {
+        match self.yallop_q() {
+            q if q > 0.216 => Yallop::A,
+            q if q > -0.014 => Yallop::B,
+            q if q > -0.160 => Yallop::C,
+            q if q > -0.232 => Yallop::D,
+            q if q > -0.293 => Yallop::E,
+            _ => Yallop::F,
+        }
+    }
+    /// V value of Odeh, with the topocentric ARCV and width
+    pub fn odeh_v(&self) -> f64 {
+        self.topocentric_arc_of_vision - minimum_arc_of_vision(self.topocentric_width, 7.1651)
+    }
+    pub fn odeh(&self) -> Odeh {
+        match self.odeh_v() {
+            v if v >= 5.65 => Odeh::NakedEye,
+            v if v >= 2.0 => Odeh::OpticalAidOrNakedEye,
+            v if v >= -0.96 => Odeh::OpticalAid,
+            _ => Odeh::NotVisible,
+        }
+    }
+    /// MABIMS 2021 (Indonesia, Malaysia, Brunei, Singapore): altitude of 3° and elongation of 6.4°
+    pub fn mabims(&self) -> bool {
+        self.moon_altitude >= 3.0 && self.elongation >= 6.4
+    }
+    /// Wujudul hilal (Muhammadiyah): the conjunction is before sunset, and the moon is above the horizon
+    pub fn wujudul_hilal(&self) -> bool {
+        self.age > SignedDuration::ZERO && self.moon_altitude > 0.0
+    }
+}
+
+/// ARCV under which the crescent is not seen, from the width (arcminutes)
+fn minimum_arc_of_vision(width: f64, constant: f64) -> f64 {
+    (-0.1018_f64).mul_add(
+        width.powi(3),
+        0.7319_f64.mul_add(width.powi(2), (-6.3226_f64).mul_add(width, constant)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jakarta() -> Location {
+        Location::new(-6.2088, 106.8456)
+    }
+
+    #[test]
+    fn eid_of_1444() -> Result<(), crate::Error> {
+        // Muhammadiyah celebrated Eid on 21 April 2023, and the government on 22 April
+        let visibility = Visibility::new(jakarta(), civil::date(2023, 4, 20))?;
+        let error = visibility
+            .conjunction
+            .duration_since("2023-04-20T04:12:00Z".parse()?);
+        assert!(error.abs() < SignedDuration::from_mins(1));
+        assert!((0.0..3.0).contains(&visibility.moon_altitude));
+        assert!(visibility.wujudul_hilal());
+        assert!(!visibility.mabims());
+        assert_eq!(visibility.yallop(), Yallop::F);
+        Ok(())
+    }
+    #[test]
+    fn conjunction_at_sunset() -> Result<(), crate::Error> {
+        // The conjunction was at 17:58 (WIB), a few minutes before Maghreb
+        let visibility = Visibility::new(jakarta(), civil::date(2025, 3, 29))?;
+        assert!(visibility.age > SignedDuration::ZERO);
+        assert!(visibility.age < SignedDuration::from_mins(10));
+        assert!(visibility.moon_altitude < 0.0);
+        assert!(!visibility.wujudul_hilal());
+        assert!(!visibility.mabims());
+        assert_eq!(visibility.yallop(), Yallop::F);
+        assert_eq!(visibility.odeh(), Odeh::NotVisible);
+        Ok(())
+    }
+    #[test]
+    fn day_after_conjunction() -> Result<(), crate::Error> {
+        let visibility = Visibility::new(jakarta(), civil::date(2025, 3, 30))?;
+        assert_eq!(visibility.age.as_hours(), 24);
+        assert!(visibility.elongation > 14.0);
+        assert!(visibility.arc_of_vision > 11.0);
+        assert!(visibility.wujudul_hilal());
+        assert!(visibility.mabims());
+        assert_eq!(visibility.yallop(), Yallop::A);
+        assert_eq!(visibility.odeh(), Odeh::NakedEye);
+        Ok(())
+    }
+    #[test]
+    fn no_sunset() {
+        let tromso = Location::new(69.6492, 18.9553);
+        assert!(Visibility::new(tromso, civil::date(2025, 6, 21)).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{hijri::cal, salah::Location};
+
+/// Geographical location of the Kaaba, Makkah
+pub const KAABA: Location = Location::new(21.4225, 39.8262);
+
+/// Mean radius of the earth, in km
+const EARTH_RADIUS: f32 = 6371.0088;
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Qibla {
+    location: Location,
+}
+
+impl Qibla {
+    pub const fn new(location: Location) -> Self {
+        Self { location }
+    }
+    pub const fn location(&self) -> Location {
+        self.location
+    }
+    /// Initial great-circle bearing to the Kaaba, in degrees clockwise from true north
+    pub fn direction(&self) -> f32 {
+        let (latitude, kaaba_latitude) = (self.location.latitude(), KAABA.latitude());
+        let longitude_difference = KAABA.longitude() - self.location.longitude();
+
+        let y = cal::dsin(longitude_difference) * cal::dcos(kaaba_latitude);
+        let x = cal::dcos(latitude).mul_add(
+            cal::dsin(kaaba_latitude),
+            -cal::dsin(latitude) * cal::dcos(kaaba_latitude) * cal::dcos(longitude_difference),
+        );
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+    /// Bearing to the Kaaba for a magnetic compass.
+    /// `declination` is the magnetic declination of the location in degrees, east is positive.
+    pub fn compass(&self, declination: f32) -> f32 {
+        (self.direction() - declination).rem_euclid(360.0)
+    }
+    /// Great-circle distance to the Kaaba, in km
+    pub fn distance(&self) -> f32 {
+        let (latitude, kaaba_latitude) = (self.location.latitude(), KAABA.latitude());
+        let latitude_difference = kaaba_latitude - latitude;
+        let longitude_difference = KAABA.longitude() - self.location.longitude();
+
+        // haversine formula
+        let a = (cal::dcos(latitude) * cal::dcos(kaaba_latitude)).mul_add(
+            cal::dsin(longitude_difference / 2.0).powi(2),
+            cal::dsin(latitude_difference / 2.0).powi(2),
+        );
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(val: f32) -> f32 {
+        (val * 100.0).round() / 100.0
+    }
+
+    #[test]
+    fn qibla_jakarta() {
+        let qibla = Qibla::new(Location::new(-6.2088, 106.8456));
+        assert_eq!(round(qibla.direction()), 295.15);
+        assert_eq!(qibla.distance().round(), 7920.0);
+    }
+    #[test]
+    fn qibla_new_york() {
+        let qibla = Qibla::new(Location::new(40.7128, -74.0060));
+        assert_eq!(round(qibla.direction()), 58.48);
+        assert_eq!(qibla.distance().round(), 10306.0);
+    }
+    #[test]
+    fn qibla_london() {
+        let qibla = Qibla::new(Location::new(51.5074, -0.1278));
+        assert_eq!(round(qibla.direction()), 118.99);
+        assert_eq!(qibla.distance().round(), 4794.0);
+    }
+    #[test]
+    fn compass_bearing() {
+        let qibla = Qibla::new(Location::new(-6.2088, 106.8456));
+        // magnetic north is 0.8° east of true north
+        assert_eq!(round(qibla.compass(0.8)), 294.35);
+        // magnetic north is 65° west of true north
+        assert_eq!(round(qibla.compass(-65.0)), 0.15);
+    }
+    #[test]
+    fn at_the_kaaba() {
+        assert_eq!(Qibla::new(KAABA).distance(), 0.0);
+    }
+}
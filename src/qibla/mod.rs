@@ -0,0 +1,6 @@
+mod direction;
+mod rasd;
+
+// shorter access for library consumer
+pub use direction::{KAABA, Qibla};
+pub use rasd::{QiblaShadow, Shadow};
@@ -0,0 +1,227 @@
+use jiff::{Timestamp, ToSpan, civil, tz::TimeZone};
+
+use crate::{
+    hijri::cal,
+    qibla::direction::{KAABA, Qibla},
+    salah::{Location, PrayerTimes},
+};
+
+/// Direction of the shadow of a vertical pole
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Shadow {
+    /// The sun is in the direction of the qibla, the shadow points away from Makkah
+    AwayFromQibla,
+    /// The sun is opposite the qibla, the shadow points toward Makkah
+    TowardQibla,
+}
+
+/// An instant when the shadow of a vertical pole is aligned with the qibla
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct QiblaShadow {
+    pub time: Timestamp,
+    pub shadow: Shadow,
+}
+
+impl Qibla {
+    /// Rasd al-Qibla, the instants between `start` and `end` when the sun is over the Kaaba or its antipode.
+    /// At these instants, the sun's azimuth is the qibla (or its antipode) from every place where the sun is up,
+    /// so only the ones visible from the location are returned.
+    pub fn rasd(
+        &self,
+        start: civil::Date,
+        end: civil::Date,
+    ) -> Result<Vec<QiblaShadow>, crate::Error> {
+        let mut rasd = Vec::new();
+        // The sun is over the Kaaba at noon in Makkah, and over its antipode at midnight in Makkah
+        let events = [
+            (KAABA.latitude(), 0.0, Shadow::AwayFromQibla),
+            (-KAABA.latitude(), 12.0, Shadow::TowardQibla),
+        ];
+        for (latitude, shift, shadow) in events {
+            let distance = |date: civil::Date| -> Result<(f32, f32), crate::Error> {
+                let hour = PrayerTimes::dohr(date.at(0, 0, 0, 0), KAABA)? + shift;
+                Ok((sun_declination(date, hour)? - latitude, hour))
+            };
+
+            let mut date = start - 1.day();
+            let (mut previous, _) = distance(date)?;
+            while date <= end {
+                let (current, hour) = distance(date + 1.day())?;
+                // The sun crossed the latitude, take the closest day
+                if previous.signum() != current.signum() {
+                    let (date, hour) = if previous.abs() < current.abs() {
+                        (date, distance(date)?.1)
+                    } else {
+                        (date + 1.day(), hour)
+                    };
+                    let (altitude, _) = sun_position(self.location(), date, hour)?;
+                    if (start..=end).contains(&date) && altitude > 0.0 {
+                        let time = instant(date, hour)?;
+                        rasd.push(QiblaShadow { time, shadow });
+                    }
+                }
+                previous = current;
+                date += 1.day();
+            }
+        }
+        rasd.sort_by_key(|rasd| rasd.time);
+        Ok(rasd)
+    }
+    /// The daily qibla shadow times, when the sun's azimuth is the qibla or its antipode
+    pub fn shadow_times(&self, date: civil::Date) -> Result<Vec<QiblaShadow>, crate::Error> {
+        let location = self.location();
+        let dohr = PrayerTimes::dohr(date.at(0, 0, 0, 0), location)?;
+        let qibla = self.direction();
+
+        let mut times = Vec::new();
+        for (azimuth, shadow) in [
+            (qibla, Shadow::AwayFromQibla),
+            ((qibla + 180.0).rem_euclid(360.0), Shadow::TowardQibla),
+        ] {
+            // Difference between the sun's azimuth and the wanted one, in [-180, 180)
+            let difference = |hour: f32| -> Result<f32, crate::Error> {
+                let (_, sun_azimuth) = sun_position(location, date, hour)?;
+                Ok((sun_azimuth - azimuth + 180.0).rem_euclid(360.0) - 180.0)
+            };
+
+            // Scan the day around noon every 5 minutes, then bisect
+            let step = 1.0 / 12.0;
+            let mut start = dohr - 12.0;
+            while start < dohr + 12.0 {
+                let (mut low, mut high) = (start, start + step);
+                let (low_difference, high_difference) = (difference(low)?, difference(high)?);
+                let is_crossing = low_difference.signum() != high_difference.signum()
+                    && (low_difference - high_difference).abs() < 180.0;
+                if is_crossing {
+                    for _ in 0..20 {
+                        let middle = (low + high) / 2.0;
+                        if difference(middle)?.signum() == low_difference.signum() {
+                            low = middle;
+                        } else {
+                            high = middle;
+                        }
+                    }
+                    let (altitude, _) = sun_position(location, date, low)?;
+                    if altitude > 0.0 {
+                        let time = instant(date, low)?;
+                        times.push(QiblaShadow { time, shadow });
+                    }
+                }
+                start += step;
+            }
+        }
+        times.sort_by_key(|time| time.time);
+        Ok(times)
+    }
+}
+
+/// Sun declination at `hour` (UTC) of the date
+fn sun_declination(date: civil::Date, hour: f32) -> Result<f32, crate::Error> {
+    // `cal::sun_declination` of a date is the declination at noon (UTC)
+    let today = cal::sun_declination(cal::gregorian_to_julian(date));
+    let tomorrow = cal::sun_declination(cal::gregorian_to_julian(date.tomorrow()?));
+    Ok((tomorrow - today).mul_add((hour - 12.0) / 24.0, today))
+}
+
+/// Altitude and azimuth (clockwise from true north) of the sun at `hour` (UTC) of the date
+fn sun_position(
+    location: Location,
+    date: civil::Date,
+    hour: f32,
+) -> Result<(f32, f32), crate::Error> {
+    let latitude = location.latitude();
+    let declination = sun_declination(date, hour)?;
+    let dohr = PrayerTimes::dohr(date.at(0, 0, 0, 0), location)?;
+    let hour_angle = (hour - dohr) * 15.0;
+
+    let altitude = cal::dsin(latitude)
+        .mul_add(
+            cal::dsin(declination),
+            cal::dcos(latitude) * cal::dcos(declination) * cal::dcos(hour_angle),
+        )
+        .asin()
+        .to_degrees();
+    let azimuth = (-cal::dcos(declination) * cal::dsin(hour_angle))
+        .atan2(cal::dsin(declination).mul_add(
+            cal::dcos(latitude),
+            -cal::dcos(declination) * cal::dcos(hour_angle) * cal::dsin(latitude),
+        ))
+        .to_degrees()
+        .rem_euclid(360.0);
+    Ok((altitude, azimuth))
+}
+
+/// Convert a decimal value (in UTC hours) of the date to an instant
+fn instant(date: civil::Date, hour: f32) -> Result<Timestamp, crate::Error> {
+    let midnight = date.to_zoned(TimeZone::UTC)?.timestamp();
+    Ok(Timestamp::from_second(
+        midnight.as_second() + (hour * 3600.0).round() as i64,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::Unit;
+
+    use super::*;
+
+    fn minutes(shadows: Vec<QiblaShadow>) -> Result<Vec<(Timestamp, Shadow)>, crate::Error> {
+        shadows
+            .into_iter()
+            .map(|shadow| Ok((shadow.time.round(Unit::Minute)?, shadow.shadow)))
+            .collect()
+    }
+
+    #[test]
+    fn rasd_al_qibla_jakarta() -> Result<(), crate::Error> {
+        // The sun is over the Kaaba on 27 May 12:18 and 15 July 12:27 (Makkah time),
+        // the sun is under the horizon in Jakarta when it is over the antipode.
+        let qibla = Qibla::new(Location::new(-6.2088, 106.8456));
+        let rasd = qibla.rasd(civil::date(2025, 1, 1), civil::date(2025, 12, 31))?;
+        assert_eq!(
+            minutes(rasd)?,
+            vec![
+                ("2025-05-27T09:18:00Z".parse()?, Shadow::AwayFromQibla),
+                ("2025-07-15T09:27:00Z".parse()?, Shadow::AwayFromQibla),
+            ]
+        );
+        Ok(())
+    }
+    #[test]
+    fn rasd_al_qibla_new_york() -> Result<(), crate::Error> {
+        // The sun is over the antipode of the Kaaba on 13 January 00:29 and 29 November 00:09 (Makkah time)
+        let qibla = Qibla::new(Location::new(40.7128, -74.0060));
+        let rasd = qibla.rasd(civil::date(2025, 1, 1), civil::date(2025, 12, 31))?;
+        assert_eq!(
+            minutes(rasd)?,
+            vec![
+                ("2025-01-12T21:29:00Z".parse()?, Shadow::TowardQibla),
+                ("2025-11-28T21:09:00Z".parse()?, Shadow::TowardQibla),
+            ]
+        );
+
+        let rasd = qibla.rasd(civil::date(2025, 2, 1), civil::date(2025, 11, 1))?;
+        assert!(rasd.is_empty());
+        Ok(())
+    }
+    #[test]
+    fn daily_shadow_times() -> Result<(), crate::Error> {
+        let location = Location::new(40.7128, -74.0060);
+        let qibla = Qibla::new(location);
+        let date = civil::date(2025, 5, 27);
+
+        let shadows = qibla.shadow_times(date)?;
+        assert_eq!(
+            minutes(shadows.clone())?,
+            vec![("2025-05-27T18:43:00Z".parse()?, Shadow::TowardQibla)]
+        );
+
+        // The sun's azimuth is the antipode of the qibla
+        let midnight = date.to_zoned(TimeZone::UTC)?.timestamp();
+        let hour = (shadows[0].time.as_second() - midnight.as_second()) as f32 / 3600.0;
+        let (altitude, azimuth) = sun_position(location, date, hour)?;
+        assert!(altitude > 0.0);
+        assert_eq!(azimuth.round(), (qibla.direction() + 180.0).round());
+        Ok(())
+    }
+}
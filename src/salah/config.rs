@@ -1,4 +1,18 @@
-use crate::salah::{madhab::Madhab, method::Method};
+use jiff::civil;
+
+use crate::hijri::Calendar;
+use crate::salah::{
+    high_latitude::HighLatitudeRule,
+    imsak::Imsak,
+    madhab::Madhab,
+    makruh::{Extent, MakruhExtents},
+    method::Method,
+    midnight::MidnightMethod,
+    moonsighting::Shafaq,
+    refraction::Refraction,
+    rounding::Rounding,
+    window::IshaaEnd,
+};
 
 #[derive(Debug, Copy, Clone)]
 pub struct IshaInterval {
@@ -6,18 +20,73 @@ pub struct IshaInterval {
     pub ramdan: f32,
 }
 
+/// Minutes added to each computed time (tune / ihtiyat), can be negative or fractional
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Offsets {
+    pub imsak: f32,
+    pub fajr: f32,
+    pub sherook: f32,
+    pub dohr: f32,
+    pub asr: f32,
+    pub maghreb: f32,
+    pub ishaa: f32,
+    pub midnight: f32,
+}
+
+/// Non-obligatory events taken into account by `current` and `next`
+#[derive(Debug, Default, Copy, Clone)]
+pub struct OptionalEvents {
+    pub imsak: bool,
+    pub ishraq: bool,
+    pub duha: bool,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Config {
     pub fajr_angle: f32,
     pub ishaa_angle: f32,
+    /// Maghreb is at sunset if not set
+    pub maghreb_angle: Option<f32>,
     /// fajr and ishaa method
     pub method: Method,
     /// asr madhab:
     pub madhab: Madhab,
-    /// is summer time is used in the place
-    pub is_summer: bool,
+    /// asr shadow factor, overrides the madhab one
+    pub asr_shadow: Option<f32>,
+    /// shadow factor at the end of the preferred (ikhtiyari) time of Asr
+    pub asr_preferred_shadow: f32,
     /// minutes after Maghreb
     pub isha_interval: IshaInterval,
+    /// adjustment of Fajr and Ishaa in high latitudes
+    pub high_latitude_rule: Option<HighLatitudeRule>,
+    /// minutes added to each prayer
+    pub offsets: Offsets,
+    /// rounding of the computed times
+    pub rounding: Rounding,
+    /// atmospheric refraction at Sherook, Maghreb and near the horizon
+    pub refraction: Refraction,
+    /// end of suhoor
+    pub imsak: Imsak,
+    /// events that are not obligatory prayers
+    pub optional_events: OptionalEvents,
+    /// length of the makruh windows
+    pub makruh: MakruhExtents,
+    /// end of Ishaa time
+    pub ishaa_end: IshaaEnd,
+    /// start of Ishraq after Sherook
+    pub ishraq: Extent,
+    /// start of Duha after Sherook
+    pub duha: Extent,
+    /// night used for midnight and its thirds
+    pub midnight_method: MidnightMethod,
+    /// twilight of Ishaa for the Moonsighting Committee method
+    pub shafaq: Shafaq,
+    /// Hijri calendar to find Ramadan, for the Ishaa interval
+    pub hijri_calendar: Calendar,
+    /// days added to the Hijri date
+    pub hijri_correction: i32,
+    /// fixed time of Jumuah, at Dohr if not set
+    pub jumuah: Option<civil::Time>,
 }
 
 impl Default for Config {
@@ -27,18 +96,42 @@ impl Default for Config {
 }
 
 impl Config {
+    /// Asr shadow factor, from the madhab if not set
+    pub fn asr_shadow_factor(&self) -> f32 {
+        self.asr_shadow
+            .unwrap_or_else(|| self.madhab.shadow() as f32)
+    }
     pub fn new() -> Self {
         Self {
             // default
             fajr_angle: 18.0,
             ishaa_angle: 18.0,
+            maghreb_angle: None,
             method: Method::MuslimWorldLeague,
             madhab: Madhab::Shafi,
-            is_summer: false,
+            asr_shadow: None,
+            asr_preferred_shadow: 2.0,
             isha_interval: IshaInterval {
                 all_year: 0.0,
                 ramdan: 0.0,
             },
+            high_latitude_rule: None,
+            offsets: Offsets::default(),
+            rounding: Rounding::Nearest,
+            refraction: Refraction::Standard,
+            imsak: Imsak::default(),
+            optional_events: OptionalEvents::default(),
+            makruh: MakruhExtents::default(),
+            ishaa_end: IshaaEnd::Midnight,
+            ishraq: Extent::Altitude(4.0),
+            // Duha is best when a quarter of the day has passed, when the sun heats the sand
+            // (Sahih Muslim 748), Ishraq starts once the sun has risen a spear's length
+            duha: Extent::DayFraction(0.25),
+            midnight_method: MidnightMethod::MaghrebToFajr,
+            shafaq: Shafaq::General,
+            hijri_calendar: Calendar::default(),
+            hijri_correction: 0,
+            jumuah: None,
         }
     }
     pub fn with(&self, method: Method, madhab: Madhab) -> Self {
@@ -56,8 +149,19 @@ impl Config {
         self.ishaa_angle = isha;
         self
     }
-    pub fn is_summer(mut self, is_summer: bool) -> Self {
-        self.is_summer = is_summer;
+    /// Depression angle of the sun at Maghreb
+    pub fn maghreb_angle(mut self, angle: f32) -> Self {
+        self.maghreb_angle = Some(angle);
+        self
+    }
+    /// Shadow factor of Asr, instead of the madhab one
+    pub fn asr_shadow(mut self, factor: f32) -> Self {
+        self.asr_shadow = Some(factor);
+        self
+    }
+    /// Shadow factor at the end of the preferred time of Asr
+    pub fn asr_preferred_shadow(mut self, factor: f32) -> Self {
+        self.asr_preferred_shadow = factor;
         self
     }
     pub fn isha_interval(mut self, isha_interval: IshaInterval) -> Self {
@@ -65,6 +169,63 @@ impl Config {
         self.isha_interval = isha_interval;
         self
     }
+    pub fn high_latitude_rule(mut self, rule: HighLatitudeRule) -> Self {
+        self.high_latitude_rule = Some(rule);
+        self
+    }
+    pub fn offsets(mut self, offsets: Offsets) -> Self {
+        self.offsets = offsets;
+        self
+    }
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+    pub fn refraction(mut self, refraction: Refraction) -> Self {
+        self.refraction = refraction;
+        self
+    }
+    pub fn imsak(mut self, imsak: Imsak) -> Self {
+        self.imsak = imsak;
+        self
+    }
+    pub fn optional_events(mut self, events: OptionalEvents) -> Self {
+        self.optional_events = events;
+        self
+    }
+    pub fn makruh(mut self, extents: MakruhExtents) -> Self {
+        self.makruh = extents;
+        self
+    }
+    pub fn ishaa_end(mut self, end: IshaaEnd) -> Self {
+        self.ishaa_end = end;
+        self
+    }
+    /// Ishraq and Duha start
+    pub fn voluntary(mut self, ishraq: Extent, duha: Extent) -> Self {
+        self.ishraq = ishraq;
+        self.duha = duha;
+        self
+    }
+    pub fn midnight_method(mut self, method: MidnightMethod) -> Self {
+        self.midnight_method = method;
+        self
+    }
+    pub fn shafaq(mut self, shafaq: Shafaq) -> Self {
+        self.shafaq = shafaq;
+        self
+    }
+    /// Hijri calendar and its correction in days
+    pub fn hijri_calendar(mut self, calendar: Calendar, correction: i32) -> Self {
+        self.hijri_calendar = calendar;
+        self.hijri_correction = correction;
+        self
+    }
+    /// Fixed time of Jumuah, in the time zone of the schedule
+    pub fn jumuah(mut self, time: civil::Time) -> Self {
+        self.jumuah = Some(time);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -78,4 +239,13 @@ mod tests {
 
         assert_eq!(config.method, Method::Egyptian);
     }
+
+    #[test]
+    fn asr_shadow() {
+        let config = Config::new().with(Method::Egyptian, Madhab::Hanafi);
+        assert_eq!(config.asr_shadow_factor(), 2.0);
+
+        let config = config.asr_shadow(1.5);
+        assert_eq!(config.asr_shadow_factor(), 1.5);
+    }
 }
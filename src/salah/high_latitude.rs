@@ -0,0 +1,37 @@
+/// How to place Fajr and Ishaa when the sun never reaches the twilight angle,
+/// or reaches it too late in the night (e.g. London or Oslo in June)
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum HighLatitudeRule {
+    /// Fajr and Ishaa are no further than half of the night from sunrise and sunset
+    MiddleOfTheNight,
+    /// Fajr and Ishaa are no further than a seventh of the night from sunrise and sunset
+    SeventhOfTheNight,
+    /// The night is divided by the twilight angle: `angle / 60` of the night
+    TwilightAngle,
+    /// Use the times of the given latitude (e.g. 48.5°) when the sun never reaches the angle
+    NearestLatitude(f32),
+}
+
+impl HighLatitudeRule {
+    /// Portion of the night for the given twilight angle
+    pub fn portion(self, angle: f32) -> Option<f32> {
+        match self {
+            Self::MiddleOfTheNight => Some(1.0 / 2.0),
+            Self::SeventhOfTheNight => Some(1.0 / 7.0),
+            Self::TwilightAngle => Some(angle / 60.0),
+            Self::NearestLatitude(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn night_portion() {
+        assert_eq!(HighLatitudeRule::MiddleOfTheNight.portion(18.0), Some(0.5));
+        assert_eq!(HighLatitudeRule::TwilightAngle.portion(18.0), Some(0.3));
+        assert_eq!(HighLatitudeRule::NearestLatitude(48.5).portion(18.0), None);
+    }
+}
@@ -0,0 +1,14 @@
+/// When to stop eating before Fajr (end of suhoor)
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Imsak {
+    /// Minutes before Fajr
+    Minutes(f32),
+    /// Depression angle of the sun, like Fajr angle
+    Angle(f32),
+}
+
+impl Default for Imsak {
+    fn default() -> Self {
+        Self::Minutes(10.0)
+    }
+}
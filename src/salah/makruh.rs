@@ -0,0 +1,41 @@
+/// Times when starting a voluntary prayer is disliked (makruh)
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Makruh {
+    /// From Sherook until the sun has risen a spear's length
+    Sunrise,
+    /// Just before Dohr, when the sun is at its zenith
+    Zawal,
+    /// From the yellowing of the sun until Maghreb
+    Sunset,
+}
+
+/// Distance of a time from a prayer time, in minutes, by the altitude of the sun,
+/// or as a fraction of the day. It bounds the makruh windows, and places Ishraq and Duha after Sherook.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Extent {
+    /// Minutes from (or until) the prayer time
+    Minutes(f32),
+    /// Altitude of the sun in degrees, above the horizon for Sunrise and Sunset,
+    /// and below its highest altitude for Zawal
+    Altitude(f32),
+    /// Fraction of the day, from Sherook to Maghreb
+    DayFraction(f32),
+}
+
+/// Length of each makruh window
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct MakruhExtents {
+    pub sunrise: Extent,
+    pub zawal: Extent,
+    pub sunset: Extent,
+}
+
+impl Default for MakruhExtents {
+    fn default() -> Self {
+        Self {
+            sunrise: Extent::Minutes(15.0),
+            zawal: Extent::Minutes(10.0),
+            sunset: Extent::Minutes(15.0),
+        }
+    }
+}
@@ -1,4 +1,11 @@
-use crate::salah::config::{Config, IshaInterval};
+use crate::{
+    hijri::Calendar,
+    salah::{
+        config::{Config, IshaInterval, Offsets},
+        midnight::MidnightMethod,
+        rounding::Rounding,
+    },
+};
 
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub enum Method {
@@ -9,7 +16,6 @@ pub enum Method {
 
     /// Muslim World League (MWL)
     /// Ministry of Religious Affairs and Awqaf, Algeria
-    /// Presidency of Religious Affairs, Turkey
     MuslimWorldLeague,
 
     /// Egyptian General Authority of Survey (EGAS)
@@ -26,8 +32,6 @@ pub enum Method {
     French,
 
     /// Islamic Religious Council of Singapore (MUIS)
-    /// Department of Islamic Advancements of Malaysia (JAKIM)
-    // Ministry of Religious Affairs of Indonesia (KEMENAG)
     Singapore,
 
     /// Spiritual Administration of Muslims of Russia
@@ -35,6 +39,44 @@ pub enum Method {
 
     /// Fixed Ishaa Time Interval, 90min
     FixedInterval,
+
+    /// Shia Ithna-Ashari, Leva Institute, Qum
+    Jafari,
+
+    /// Institute of Geophysics, University of Tehran
+    Tehran,
+
+    /// Gulf Region, Ishaa 90min after Maghreb
+    Gulf,
+
+    /// Kuwait
+    Kuwait,
+
+    /// Qatar, Ishaa 90min after Maghreb
+    Qatar,
+
+    /// Islamic Affairs and Charitable Activities Department, Dubai (IACAD)
+    Dubai,
+
+    /// Presidency of Religious Affairs, Turkey (Diyanet), with its temkin offsets
+    Turkey,
+
+    /// Department of Islamic Advancements of Malaysia (JAKIM), its angles only,
+    /// the ihtiyat of the official timetable is not applied
+    Malaysia,
+
+    /// Ministry of Religious Affairs of Indonesia (KEMENAG), with 2min of ihtiyat
+    Indonesia,
+
+    /// Ministry of Habous and Islamic Affairs, Morocco, its angles only,
+    /// the minute adjustments of the official timetable are not applied
+    Morocco,
+
+    /// Ministry of Awqaf, Islamic Affairs and Holy Places, Jordan
+    Jordan,
+
+    /// Moonsighting Committee Worldwide (MCW), seasonal Fajr and Ishaa
+    MoonsightingCommittee,
 }
 
 impl Method {
@@ -44,15 +86,15 @@ impl Method {
             Self::Karachi => Config::new().angle(18.0, 18.0).method(self),
             Self::MuslimWorldLeague => Config::new().angle(18.0, 17.0).method(self),
             Self::Egyptian => Config::new().angle(19.5, 17.5).method(self),
-            Self::UmmAlQura => {
-                Config::new()
-                    .angle(18.5, 0.0)
-                    .method(self)
-                    .isha_interval(IshaInterval {
-                        all_year: 90.0,
-                        ramdan: 120.0,
-                    })
-            }
+            // Ramadan follows the official calendar
+            Self::UmmAlQura => Config::new()
+                .angle(18.5, 0.0)
+                .method(self)
+                .isha_interval(IshaInterval {
+                    all_year: 90.0,
+                    ramdan: 120.0,
+                })
+                .hijri_calendar(Calendar::UmmAlQura, 0),
             Self::NorthAmerica => Config::new().angle(15.0, 15.0).method(self),
             Self::French => Config::new().angle(12.0, 12.0).method(self),
             Self::Singapore => Config::new().angle(20.0, 18.0).method(self),
@@ -66,6 +108,86 @@ impl Method {
                         ramdan: 120.0,
                     })
             }
+            Self::Jafari => Config::new()
+                .angle(16.0, 14.0)
+                .method(self)
+                .maghreb_angle(4.0)
+                .midnight_method(MidnightMethod::Jafari),
+            Self::Tehran => Config::new()
+                .angle(17.7, 14.0)
+                .method(self)
+                .maghreb_angle(4.5)
+                .midnight_method(MidnightMethod::Jafari),
+            Self::Gulf => Config::new()
+                .angle(19.5, 0.0)
+                .method(self)
+                .isha_interval(IshaInterval {
+                    all_year: 90.0,
+                    ramdan: 90.0,
+                }),
+            Self::Kuwait => Config::new().angle(18.0, 17.5).method(self),
+            Self::Qatar => {
+                Config::new()
+                    .angle(18.0, 0.0)
+                    .method(self)
+                    .isha_interval(IshaInterval {
+                        all_year: 90.0,
+                        ramdan: 90.0,
+                    })
+            }
+            Self::Dubai => Config::new()
+                .angle(18.2, 18.2)
+                .method(self)
+                .offsets(Offsets {
+                    sherook: -3.0,
+                    dohr: 3.0,
+                    asr: 3.0,
+                    maghreb: 3.0,
+                    ..Default::default()
+                }),
+            Self::Turkey => Config::new()
+                .angle(18.0, 17.0)
+                .method(self)
+                .offsets(Offsets {
+                    sherook: -7.0,
+                    dohr: 5.0,
+                    asr: 4.0,
+                    maghreb: 7.0,
+                    ..Default::default()
+                }),
+            Self::Malaysia => Config::new().angle(20.0, 18.0).method(self),
+            Self::Indonesia => Config::new()
+                .angle(20.0, 18.0)
+                .method(self)
+                .offsets(Offsets {
+                    fajr: 2.0,
+                    sherook: -2.0,
+                    dohr: 2.0,
+                    asr: 2.0,
+                    maghreb: 2.0,
+                    ishaa: 2.0,
+                    ..Default::default()
+                })
+                .rounding(Rounding::Up),
+            Self::Morocco => Config::new().angle(19.0, 17.0).method(self),
+            Self::Jordan => Config::new()
+                .angle(18.0, 18.0)
+                .method(self)
+                .offsets(Offsets {
+                    maghreb: 5.0,
+                    ..Default::default()
+                }),
+            // The angles are only used for the high latitude rules
+            Self::MoonsightingCommittee => {
+                Config::new()
+                    .angle(18.0, 18.0)
+                    .method(self)
+                    .offsets(Offsets {
+                        dohr: 5.0,
+                        maghreb: 3.0,
+                        ..Default::default()
+                    })
+            }
         }
     }
 }
@@ -89,4 +211,51 @@ mod tests {
 
         assert_eq!(params.method, Method::Egyptian);
     }
+
+    #[test]
+    fn configs_for_jafari() {
+        let params = Method::Jafari.configs();
+
+        assert_eq!(params.maghreb_angle, Some(4.0));
+        assert_eq!(params.midnight_method, MidnightMethod::Jafari);
+    }
+
+    #[test]
+    fn configs_with_interval() {
+        for method in [Method::Gulf, Method::Qatar] {
+            let params = method.configs();
+
+            assert_eq!(params.ishaa_angle, 0.0);
+            assert_eq!(params.isha_interval.all_year, 90.0);
+            assert_eq!(params.isha_interval.ramdan, 90.0);
+        }
+    }
+
+    #[test]
+    fn configs_for_turkey() {
+        let params = Method::Turkey.configs();
+
+        assert_eq!((params.fajr_angle, params.ishaa_angle), (18.0, 17.0));
+        assert_eq!(params.offsets.sherook, -7.0);
+        assert_eq!(params.offsets.dohr, 5.0);
+        assert_eq!(params.offsets.asr, 4.0);
+        assert_eq!(params.offsets.maghreb, 7.0);
+    }
+
+    #[test]
+    fn configs_for_dubai() {
+        let params = Method::Dubai.configs();
+
+        assert_eq!((params.fajr_angle, params.ishaa_angle), (18.2, 18.2));
+        assert_eq!(params.offsets.sherook, -3.0);
+        assert_eq!(params.offsets.maghreb, 3.0);
+    }
+
+    #[test]
+    fn configs_for_jordan() {
+        let params = Method::Jordan.configs();
+
+        assert_eq!(params.offsets.maghreb, 5.0);
+        assert_eq!(params.offsets.ishaa, 0.0);
+    }
 }
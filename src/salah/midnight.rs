@@ -0,0 +1,10 @@
+/// How the night is measured for midnight and its thirds
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum MidnightMethod {
+    /// From Maghreb to Fajr
+    MaghrebToFajr,
+    /// From Maghreb to Sherook
+    MaghrebToSherook,
+    /// From sunset to Fajr
+    Jafari,
+}
@@ -1,14 +1,30 @@
 #![allow(clippy::excessive_precision)]
 
 mod config;
+mod high_latitude;
+mod imsak;
 mod madhab;
+mod makruh;
 mod method;
+mod midnight;
+mod moonsighting;
 mod prayer;
+mod refraction;
+mod rounding;
 mod times;
+mod window;
 
 // shorter access for library consumer
-pub use config::Config;
+pub use config::{Config, Offsets, OptionalEvents};
+pub use high_latitude::HighLatitudeRule;
+pub use imsak::Imsak;
 pub use madhab::Madhab;
+pub use makruh::{Extent, Makruh, MakruhExtents};
 pub use method::Method;
+pub use midnight::MidnightMethod;
+pub use moonsighting::Shafaq;
 pub use prayer::Prayer;
-pub use times::{Location, PrayerSchedule, PrayerTimes};
+pub use refraction::Refraction;
+pub use rounding::Rounding;
+pub use times::{Location, PrayerSchedule, PrayerTimes, ZonedPrayerTimes};
+pub use window::{IshaaEnd, PrayerWindow};
@@ -0,0 +1,113 @@
+use jiff::civil;
+
+/// Twilight used by the Moonsighting Committee for Ishaa
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Shafaq {
+    /// Mix of Ahmer and Abyad, depending on the season
+    General,
+    /// Red twilight
+    Ahmer,
+    /// White twilight
+    Abyad,
+}
+
+/// Minutes from Fajr to Sherook, according to the Moonsighting Committee seasonal formula
+pub(crate) fn morning_twilight(latitude: f32, date: civil::Date) -> f32 {
+    let days = days_since_solstice(latitude, date);
+    let latitude = latitude.abs();
+    let a = 75.0 + 28.65 / 55.0 * latitude;
+    let b = 75.0 + 19.44 / 55.0 * latitude;
+    let c = 75.0 + 32.74 / 55.0 * latitude;
+    let d = 75.0 + 48.10 / 55.0 * latitude;
+    seasonal(a, b, c, d, days)
+}
+
+/// Minutes from Maghreb to Ishaa, according to the Moonsighting Committee seasonal formula
+pub(crate) fn evening_twilight(latitude: f32, date: civil::Date, shafaq: Shafaq) -> f32 {
+    let days = days_since_solstice(latitude, date);
+    let latitude = latitude.abs();
+    let (a, b, c, d) = match shafaq {
+        Shafaq::General => (
+            75.0 + 25.60 / 55.0 * latitude,
+            75.0 + 2.050 / 55.0 * latitude,
+            75.0 - 9.210 / 55.0 * latitude,
+            75.0 + 6.140 / 55.0 * latitude,
+        ),
+        Shafaq::Ahmer => (
+            62.0 + 17.40 / 55.0 * latitude,
+            62.0 - 7.160 / 55.0 * latitude,
+            62.0 + 5.120 / 55.0 * latitude,
+            62.0 + 19.44 / 55.0 * latitude,
+        ),
+        Shafaq::Abyad => (
+            75.0 + 25.60 / 55.0 * latitude,
+            75.0 + 7.160 / 55.0 * latitude,
+            75.0 + 36.84 / 55.0 * latitude,
+            75.0 + 81.84 / 55.0 * latitude,
+        ),
+    };
+    seasonal(a, b, c, d, days)
+}
+
+/// Interpolate between the values at the winter solstice (a), the equinox (b),
+/// 46 days later (c) and the summer solstice (d)
+fn seasonal(a: f32, b: f32, c: f32, d: f32, days: i16) -> f32 {
+    let days = days as f32;
+    if days < 91.0 {
+        a + (b - a) / 91.0 * days
+    } else if days < 137.0 {
+        b + (c - b) / 46.0 * (days - 91.0)
+    } else if days < 183.0 {
+        c + (d - c) / 46.0 * (days - 137.0)
+    } else if days < 229.0 {
+        d + (c - d) / 46.0 * (days - 183.0)
+    } else if days < 275.0 {
+        c + (b - c) / 46.0 * (days - 229.0)
+    } else {
+        b + (a - b) / 91.0 * (days - 275.0)
+    }
+}
+
+/// Days since the winter solstice of the hemisphere
+fn days_since_solstice(latitude: f32, date: civil::Date) -> i16 {
+    let days_in_year = date.days_in_year();
+    let days = if latitude >= 0.0 {
+        date.day_of_year() + 10
+    } else {
+        date.day_of_year() - if date.in_leap_year() { 173 } else { 172 }
+    };
+    days.rem_euclid(days_in_year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solstice() {
+        assert_eq!(days_since_solstice(40.0, civil::date(2025, 12, 21)), 0);
+        assert_eq!(days_since_solstice(40.0, civil::date(2025, 1, 1)), 11);
+        assert_eq!(days_since_solstice(-6.0, civil::date(2025, 6, 21)), 0);
+        assert_eq!(days_since_solstice(-6.0, civil::date(2024, 6, 21)), 0);
+    }
+    #[test]
+    fn twilight() {
+        let date = civil::date(2025, 3, 12);
+        assert_eq!(format!("{:.2}", morning_twilight(-6.10, date)), "77.51");
+        assert_eq!(
+            format!("{:.2}", evening_twilight(-6.10, date, Shafaq::General)),
+            "74.93"
+        );
+        // At the winter solstice
+        let date = civil::date(2025, 12, 21);
+        assert_eq!(format!("{:.2}", morning_twilight(55.0, date)), "103.65");
+        assert_eq!(
+            format!("{:.2}", evening_twilight(55.0, date, Shafaq::Ahmer)),
+            "79.40"
+        );
+        assert_eq!(
+            format!("{:.2}", evening_twilight(55.0, date, Shafaq::Abyad)),
+            "100.60"
+        );
+    }
+}
@@ -1,13 +1,14 @@
-use jiff::civil::Weekday;
-
-use crate::time::today;
-
-// only obligatory prayer
+// obligatory prayers, voluntary prayers and the optional events
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Prayer {
+    Imsak,
     Fajr,
     Sherook,
+    Ishraq,
+    Duha,
     Dohr,
+    /// Friday prayer, instead of Dohr
+    Jumuah,
     Asr,
     Maghreb,
     Ishaa,
@@ -17,21 +18,32 @@ pub enum Prayer {
 impl Prayer {
     pub fn name(self) -> String {
         let prayer_name = match self {
+            Self::Imsak => "Imsak",
             Self::Fajr | Prayer::FajrTomorrow => "Fajr",
             Self::Sherook => "Sherook",
-            Self::Dohr => {
-                if today().weekday() == Weekday::Friday {
-                    "Jumua"
-                } else {
-                    "Dohr"
-                }
-            }
+            Self::Ishraq => "Ishraq",
+            Self::Duha => "Duha",
+            Self::Dohr => "Dohr",
+            Self::Jumuah => "Jumua",
             Self::Asr => "Asr",
             Self::Maghreb => "Maghreb",
             Self::Ishaa => "Ishaa",
         };
         prayer_name.to_string()
     }
+    /// Whether it is one of the five daily prayers
+    pub const fn is_obligatory(self) -> bool {
+        matches!(
+            self,
+            Self::Fajr
+                | Self::Dohr
+                | Self::Jumuah
+                | Self::Asr
+                | Self::Maghreb
+                | Self::Ishaa
+                | Self::FajrTomorrow
+        )
+    }
 }
 
 #[cfg(test)]
@@ -40,19 +52,26 @@ mod tests {
 
     #[test]
     fn prayer_name() -> Result<(), crate::Error> {
+        assert_eq!(Prayer::Imsak.name(), "Imsak");
         assert_eq!(Prayer::Fajr.name(), "Fajr");
         assert_eq!(Prayer::Sherook.name(), "Sherook");
-
-        if today().weekday() == Weekday::Friday {
-            assert_eq!(Prayer::Dohr.name(), "Jumua");
-        } else {
-            assert_eq!(Prayer::Dohr.name(), "Dohr");
-        }
+        assert_eq!(Prayer::Dohr.name(), "Dohr");
+        assert_eq!(Prayer::Jumuah.name(), "Jumua");
 
         assert_eq!(Prayer::Asr.name(), "Asr");
         assert_eq!(Prayer::Maghreb.name(), "Maghreb");
         assert_eq!(Prayer::Ishaa.name(), "Ishaa");
+        assert_eq!(Prayer::Ishraq.name(), "Ishraq");
+        assert_eq!(Prayer::Duha.name(), "Duha");
 
         Ok(())
     }
+    #[test]
+    fn obligatory_prayers() {
+        assert!(Prayer::Fajr.is_obligatory());
+        assert!(Prayer::Dohr.is_obligatory());
+        assert!(!Prayer::Sherook.is_obligatory());
+        assert!(!Prayer::Duha.is_obligatory());
+        assert!(!Prayer::Imsak.is_obligatory());
+    }
 }
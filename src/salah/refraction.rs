@@ -0,0 +1,95 @@
+/// Atmospheric refraction model, it lifts the sun above its geometric position near the horizon.
+/// It applies to Sherook and Maghreb, and to the twilight angles of Fajr, Ishaa, Imsak and
+/// `Config::maghreb_angle` that are less than the horizon refraction below the horizon.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Refraction {
+    /// Standard atmosphere, 34' at the horizon
+    Standard,
+    /// Bennett's formula, with pressure in millibars and temperature in °C
+    Bennett { pressure: f32, temperature: f32 },
+    /// Sæmundsson's formula, with pressure in millibars and temperature in °C
+    Saemundsson { pressure: f32, temperature: f32 },
+    /// No refraction, the geometric horizon is used
+    None,
+}
+
+impl Refraction {
+    /// Refraction at the horizon, in degrees
+    pub fn horizon(self) -> f32 {
+        match self {
+            Self::Standard => 34.0 / 60.0,
+            // Refraction of an apparent altitude of 0°
+            Self::Bennett {
+                pressure,
+                temperature,
+            } => {
+                let refraction = 1.0 / (7.31_f32 / 4.4).to_radians().tan();
+                Self::correction(pressure, temperature) * refraction / 60.0
+            }
+            // Sæmundsson's formula uses the true altitude, find the one that appears at 0°
+            Self::Saemundsson {
+                pressure,
+                temperature,
+            } => {
+                let mut refraction: f32 = 0.0;
+                for _ in 0..10 {
+                    let altitude = -refraction;
+                    let arcminutes =
+                        1.02 / (altitude + 10.3 / (altitude + 5.11)).to_radians().tan();
+                    refraction = Self::correction(pressure, temperature) * arcminutes / 60.0;
+                }
+                refraction
+            }
+            Self::None => 0.0,
+        }
+    }
+    /// Correction for non standard pressure and temperature
+    fn correction(pressure: f32, temperature: f32) -> f32 {
+        (pressure / 1010.0) * (283.0 / (273.0 + temperature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arcminutes(refraction: Refraction) -> String {
+        format!("{:.1}", refraction.horizon() * 60.0)
+    }
+
+    #[test]
+    fn standard_refraction() {
+        assert_eq!(arcminutes(Refraction::Standard), "34.0");
+        assert_eq!(arcminutes(Refraction::None), "0.0");
+    }
+    #[test]
+    fn bennett_refraction() {
+        let standard = Refraction::Bennett {
+            pressure: 1010.0,
+            temperature: 10.0,
+        };
+        assert_eq!(arcminutes(standard), "34.5");
+
+        // A cold and dense air bends the light more
+        let cold = Refraction::Bennett {
+            pressure: 1030.0,
+            temperature: -20.0,
+        };
+        assert_eq!(arcminutes(cold), "39.3");
+    }
+    #[test]
+    fn saemundsson_refraction() {
+        let standard = Refraction::Saemundsson {
+            pressure: 1010.0,
+            temperature: 10.0,
+        };
+        assert_eq!(arcminutes(standard), "34.4");
+
+        // Bandung, 700 m above the sea
+        let bandung = Refraction::Saemundsson {
+            pressure: 930.0,
+            temperature: 25.0,
+        };
+        assert_eq!(arcminutes(bandung), "29.4");
+    }
+}
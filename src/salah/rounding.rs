@@ -0,0 +1,47 @@
+/// How the computed times are rounded to the minute
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Rounding {
+    /// Round to the nearest minute
+    Nearest,
+    /// Always round up
+    Up,
+    /// Always round down
+    Down,
+    /// Round up the start of prayers, and round down the times that end them (e.g. Sherook)
+    Safe,
+    /// Keep the seconds
+    None,
+}
+
+impl Rounding {
+    /// Rounding for the start of a prayer
+    pub const fn start(self) -> Self {
+        match self {
+            Self::Safe => Self::Up,
+            rounding => rounding,
+        }
+    }
+    /// Rounding for a time that ends a prayer
+    pub const fn end(self) -> Self {
+        match self {
+            Self::Safe => Self::Down,
+            rounding => rounding,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_rounding() {
+        assert_eq!(Rounding::Safe.start(), Rounding::Up);
+        assert_eq!(Rounding::Safe.end(), Rounding::Down);
+    }
+    #[test]
+    fn plain_rounding() {
+        assert_eq!(Rounding::Nearest.start(), Rounding::Nearest);
+        assert_eq!(Rounding::None.end(), Rounding::None);
+    }
+}
@@ -1,10 +1,25 @@
-use std::f32::consts::PI;
+use std::{f32::consts::PI, ops::Range};
 
-use jiff::{ToSpan, Unit, civil};
+use jiff::{
+    RoundMode, SignedDuration, Timestamp, TimestampRound, ToSpan, Unit, Zoned,
+    civil::{self, Weekday},
+    tz::TimeZone,
+};
 
 use crate::{
-    hijri::{HijriDate, cal},
-    salah::{config::Config, prayer::Prayer},
+    hijri::{Calendar, cal},
+    salah::{
+        config::Config,
+        high_latitude::HighLatitudeRule,
+        imsak::Imsak,
+        makruh::{Extent, Makruh},
+        method::Method,
+        midnight::MidnightMethod,
+        moonsighting,
+        prayer::Prayer,
+        rounding::Rounding,
+        window::{IshaaEnd, PrayerWindow},
+    },
     time,
 };
 
@@ -14,37 +29,57 @@ pub struct Location {
     latitude: f32,
     /// geographical longitude of the given location
     longitude: f32,
+    /// elevation above the surrounding terrain, in metres
+    elevation: f32,
 }
 
 impl Location {
-    pub fn new(latitude: f32, longitude: f32) -> Self {
+    pub const fn new(latitude: f32, longitude: f32) -> Self {
         Self {
             latitude,
             longitude,
+            elevation: 0.0,
         }
     }
+    /// Elevation in metres, it lowers the horizon of Sherook, Maghreb and the twilight angles
+    pub const fn with_elevation(mut self, elevation: f32) -> Self {
+        self.elevation = elevation;
+        self
+    }
+    pub const fn latitude(&self) -> f32 {
+        self.latitude
+    }
+    pub const fn longitude(&self) -> f32 {
+        self.longitude
+    }
+    pub const fn elevation(&self) -> f32 {
+        self.elevation
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct PrayerSchedule {
     location: Location,
-    time: civil::DateTime,
+    date: Option<civil::Date>,
     custom_time: Option<civil::DateTime>,
     config: Config,
+    timezone: TimeZone,
 }
 
 impl PrayerSchedule {
-    pub fn new(location: Location) -> Self {
+    /// The time zone of the location gives the offset of the times, and their date
+    pub fn new(location: Location, timezone: TimeZone) -> Self {
         Self {
             location,
-            time: time::now().into(),
+            date: None,
             custom_time: None,
             // default config
             config: Config::new(),
+            timezone,
         }
     }
     pub fn on(mut self, date: civil::Date) -> Result<Self, crate::Error> {
-        self.time = date.at(0, 0, 0, 0);
+        self.date = Some(date);
         Ok(self)
     }
     pub const fn at(mut self, time: civil::DateTime) -> Self {
@@ -56,26 +91,75 @@ impl PrayerSchedule {
         self
     }
     pub fn calculate(&self) -> Result<PrayerTimes, crate::Error> {
-        PrayerTimes::new(self.time, self.location, self.config, self.custom_time)
+        let time = match self.date {
+            None => time::now().with_time_zone(self.timezone.clone()).datetime(),
+            Some(date) => date.at(0, 0, 0, 0),
+        };
+        PrayerTimes::new(
+            time,
+            self.location,
+            self.config,
+            self.custom_time,
+            self.timezone.clone(),
+        )
+    }
+    /// Calculate the prayer times as instants in the schedule's time zone
+    pub fn calculate_zoned(&self) -> Result<ZonedPrayerTimes, crate::Error> {
+        Ok(ZonedPrayerTimes::new(self.calculate()?))
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct PrayerTimes {
     custom_time: Option<civil::DateTime>,
     pub time: civil::DateTime,
     pub location: Location,
     pub config: Config,
+    pub timezone: TimeZone,
+    pub imsak: civil::DateTime,
     pub dohr: civil::DateTime,
+    /// only on Fridays
+    pub jumuah: Option<civil::DateTime>,
     pub asr: civil::DateTime,
     pub maghreb: civil::DateTime,
     pub ishaa: civil::DateTime,
     pub fajr: civil::DateTime,
     pub fajr_tomorrow: civil::DateTime,
     pub sherook: civil::DateTime,
+    pub ishraq: civil::DateTime,
+    pub duha: civil::DateTime,
     pub first_third_of_night: civil::DateTime,
     pub midnight: civil::DateTime,
     pub last_third_of_night: civil::DateTime,
+    /// times moved by the high latitude rule
+    adjusted: Vec<Prayer>,
+    /// times when voluntary prayers are disliked
+    makruh: Vec<(Makruh, Range<civil::DateTime>)>,
+    /// start of the zawal makruh window, the end of Ishraq and Duha
+    zawal: civil::DateTime,
+    /// end of the preferred time of Asr
+    asr_preferred_end: Option<civil::DateTime>,
+    /// the instants of the times, the civil times are ambiguous in the repeated hour
+    instants: Instants,
+}
+
+/// Instants of the times of `PrayerTimes`, as computed before any conversion to the time zone
+#[derive(Debug, Clone)]
+struct Instants {
+    imsak: Timestamp,
+    fajr: Timestamp,
+    sherook: Timestamp,
+    ishraq: Timestamp,
+    duha: Timestamp,
+    dohr: Timestamp,
+    jumuah: Option<Timestamp>,
+    asr: Timestamp,
+    maghreb: Timestamp,
+    ishaa: Timestamp,
+    fajr_tomorrow: Timestamp,
+    first_third_of_night: Timestamp,
+    midnight: Timestamp,
+    last_third_of_night: Timestamp,
 }
 
 impl PrayerTimes {
@@ -84,66 +168,199 @@ impl PrayerTimes {
         location: Location,
         config: Config,
         custom_time: Option<civil::DateTime>,
+        timezone: TimeZone,
     ) -> Result<Self, crate::Error> {
         let time = match custom_time {
             None => time,
             Some(custom) => custom,
         };
 
+        // offsets are in minutes
+        let rounding = config.rounding;
+        let to_time = |time, val, offset: f32, rounding| {
+            Self::hours_to_time(time, val, offset * 60.0, rounding, &timezone)
+        };
+
         // dohr time must be calculated at first, every other time depends on it!
         let dohr_time = Self::dohr(time, location)?;
-        let dohr = Self::hours_to_time(time, dohr_time, 0.0, config);
+        let dohr = to_time(time, dohr_time, config.offsets.dohr, rounding.start())?;
+
+        // A fixed Jumuah is a civil time, it takes the earlier offset in a repeated hour
+        let jumuah = match config.jumuah {
+            _ if time.date().weekday() != Weekday::Friday => None,
+            Some(jumuah) => {
+                let jumuah = time.date().to_datetime(jumuah).to_zoned(timezone.clone())?;
+                Some(jumuah.timestamp())
+            }
+            None => Some(dohr),
+        };
 
         let asr_time = Self::asr(time, location, config)?;
-        let asr = Self::hours_to_time(time, asr_time, 0.0, config);
+        let asr = to_time(time, asr_time, config.offsets.asr, rounding.start())?;
+
+        // Shadows twice their length by default, there is no preferred end if Asr starts there (Hanafi)
+        let asr_preferred_end_time =
+            Self::asr_for_shadow(config.asr_preferred_shadow, time, location)?;
+        let asr_preferred_end = Some(to_time(
+            time,
+            asr_preferred_end_time,
+            config.offsets.asr,
+            rounding.end(),
+        )?)
+        .filter(|end| *end > asr);
 
         let maghreb_time = Self::maghreb(time, location, config)?;
-        let maghreb = Self::hours_to_time(time, maghreb_time, 0.0, config);
+        let maghreb = to_time(time, maghreb_time, config.offsets.maghreb, rounding.start())?;
 
         let ishaa_time = Self::ishaa(time, location, config)?;
-        let ishaa = Self::hours_to_time(time, ishaa_time, 0.0, config);
+        let ishaa = to_time(time, ishaa_time, config.offsets.ishaa, rounding.start())?;
 
         let fajr_time = Self::fajr(time, location, config)?;
-        let fajr = Self::hours_to_time(time, fajr_time, 0.0, config);
+        let fajr = to_time(time, fajr_time, config.offsets.fajr, rounding.start())?;
 
         let sherook_time = Self::sherook(time, location, config)?;
-        let sherook = Self::hours_to_time(time, sherook_time, 0.0, config);
+        let sherook = to_time(time, sherook_time, config.offsets.sherook, rounding.end())?;
+
+        // Imsak follows the Fajr offset when it is a number of minutes before Fajr
+        let imsak_time = Self::imsak(time, location, config)?;
+        let imsak_offset = match config.imsak {
+            Imsak::Minutes(_) => config.offsets.fajr + config.offsets.imsak,
+            Imsak::Angle(_) => config.offsets.imsak,
+        };
+        let imsak = to_time(time, imsak_time, imsak_offset, rounding.end())?;
 
-        // These must be called after ishaa, since they depends on it
         let first_third_of_night_time = Self::first_third_of_night(time, location, config)?;
-        let first_third_of_night =
-            Self::hours_to_time(time, first_third_of_night_time, 0.0, config);
+        let first_third_of_night = to_time(time, first_third_of_night_time, 0.0, rounding.start())?;
 
         let midnight_time = Self::midnight(time, location, config)?;
-        let midnight = Self::hours_to_time(time, midnight_time, 0.0, config);
+        let midnight = to_time(time, midnight_time, config.offsets.midnight, rounding.end())?;
 
         let last_third_of_night_time = Self::last_third_of_night(time, location, config)?;
-        let last_third_of_night = Self::hours_to_time(time, last_third_of_night_time, 0.0, config);
+        let last_third_of_night = to_time(time, last_third_of_night_time, 0.0, rounding.start())?;
 
         let tomorrow = time + 1.days();
         let fajr_time_tomorrow = Self::fajr(tomorrow, location, config)?;
-        let fajr_tomorrow = Self::hours_to_time(tomorrow, fajr_time_tomorrow, 0.0, config);
+        let fajr_tomorrow = to_time(
+            tomorrow,
+            fajr_time_tomorrow,
+            config.offsets.fajr,
+            rounding.start(),
+        )?;
 
-        Ok(Self {
-            custom_time,
-            time,
-            location,
-            config,
+        // Windows are widened by the rounding, like the Sherook
+        let extents = config.makruh;
+        let (sunrise_time, sunrise_offset) =
+            Self::makruh_bound(Makruh::Sunrise, extents.sunrise, time, location, config)?;
+        let sunrise = to_time(time, sunrise_time, sunrise_offset, rounding.start())?;
+        let (zawal_time, zawal_offset) =
+            Self::makruh_bound(Makruh::Zawal, extents.zawal, time, location, config)?;
+        let zawal = to_time(time, zawal_time, zawal_offset, rounding.end())?;
+        let (sunset_time, sunset_offset) =
+            Self::makruh_bound(Makruh::Sunset, extents.sunset, time, location, config)?;
+        let sunset = to_time(time, sunset_time, sunset_offset, rounding.end())?;
+        // Ishraq and Duha start like the end of the sunrise makruh window
+        let (ishraq_time, ishraq_offset) =
+            Self::makruh_bound(Makruh::Sunrise, config.ishraq, time, location, config)?;
+        let ishraq = to_time(time, ishraq_time, ishraq_offset, rounding.start())?;
+        let (duha_time, duha_offset) =
+            Self::makruh_bound(Makruh::Sunrise, config.duha, time, location, config)?;
+        let duha = to_time(time, duha_time, duha_offset, rounding.start())?;
+
+        let civil = |instant: Timestamp| instant.to_zoned(timezone.clone()).datetime();
+        let makruh = vec![
+            (Makruh::Sunrise, civil(sherook)..civil(sunrise)),
+            (Makruh::Zawal, civil(zawal)..civil(dohr)),
+            (Makruh::Sunset, civil(sunset)..civil(maghreb)),
+        ];
+        let instants = Instants {
+            imsak,
+            fajr,
+            sherook,
+            ishraq,
+            duha,
             dohr,
+            jumuah,
             asr,
             maghreb,
             ishaa,
-            fajr,
             fajr_tomorrow,
-            sherook,
             first_third_of_night,
             midnight,
             last_third_of_night,
+        };
+
+        // Compare against the times without any high latitude rule
+        let unadjusted = Config {
+            high_latitude_rule: None,
+            ..config
+        };
+        let adjusted = [
+            (
+                Prayer::Imsak,
+                imsak_time,
+                Self::imsak(time, location, unadjusted)?,
+            ),
+            (
+                Prayer::Fajr,
+                fajr_time,
+                Self::fajr(time, location, unadjusted)?,
+            ),
+            (
+                Prayer::Sherook,
+                sherook_time,
+                Self::sherook(time, location, unadjusted)?,
+            ),
+            (
+                Prayer::Maghreb,
+                maghreb_time,
+                Self::maghreb(time, location, unadjusted)?,
+            ),
+            (
+                Prayer::Ishaa,
+                ishaa_time,
+                Self::ishaa(time, location, unadjusted)?,
+            ),
+            (
+                Prayer::FajrTomorrow,
+                fajr_time_tomorrow,
+                Self::fajr(tomorrow, location, unadjusted)?,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, val, unadjusted_val)| val != unadjusted_val)
+        .map(|(prayer, _, _)| prayer)
+        .collect();
+
+        Ok(Self {
+            custom_time,
+            time,
+            location,
+            config,
+            imsak: civil(imsak),
+            dohr: civil(dohr),
+            jumuah: jumuah.map(civil),
+            asr: civil(asr),
+            maghreb: civil(maghreb),
+            ishaa: civil(ishaa),
+            fajr: civil(fajr),
+            fajr_tomorrow: civil(fajr_tomorrow),
+            sherook: civil(sherook),
+            ishraq: civil(ishraq),
+            duha: civil(duha),
+            first_third_of_night: civil(first_third_of_night),
+            midnight: civil(midnight),
+            last_third_of_night: civil(last_third_of_night),
+            asr_preferred_end: asr_preferred_end.map(civil),
+            zawal: civil(zawal),
+            timezone,
+            adjusted,
+            makruh,
+            instants,
         })
     }
     /// Get the Dohr
-    fn dohr(time: civil::DateTime, location: Location) -> Result<f32, crate::Error> {
-        let longitude_difference = Self::longitude_difference(location)?;
+    pub(crate) fn dohr(time: civil::DateTime, location: Location) -> Result<f32, crate::Error> {
+        let longitude_difference = Self::longitude_difference(location);
 
         let julian_date = cal::gregorian_to_julian(time.date());
         let time_equation = cal::equation_of_time(julian_date);
@@ -151,20 +368,43 @@ impl PrayerTimes {
     }
     /// Get the Asr time
     fn asr(time: civil::DateTime, location: Location, config: Config) -> Result<f32, crate::Error> {
+        Self::asr_for_shadow(config.asr_shadow_factor(), time, location)
+    }
+    /// Get the time when shadows reach `shadow` times their length, plus their length at Dohr
+    fn asr_for_shadow(
+        shadow: f32,
+        time: civil::DateTime,
+        location: Location,
+    ) -> Result<f32, crate::Error> {
         let dohr_time = Self::dohr(time, location)?;
-        let angle = Self::asr_angle(time, location, config)?;
+        let angle = Self::asr_angle(shadow, time, location)?;
         Ok(dohr_time + Self::time_for_angle(angle, time, location)?)
     }
     /// Get the Maghreb time
-    fn maghreb(
+    pub(crate) fn maghreb(
+        time: civil::DateTime,
+        location: Location,
+        config: Config,
+    ) -> Result<f32, crate::Error> {
+        match config.maghreb_angle {
+            Some(angle) => {
+                let dohr_time = Self::dohr(time, location)?;
+                let angle = Self::twilight_angle(angle, location, config);
+                Ok(dohr_time + Self::hour_angle(angle, time, location, config)?)
+            }
+            None => Self::sunset(time, location, config),
+        }
+    }
+    /// Get the sunset time
+    fn sunset(
         time: civil::DateTime,
         location: Location,
-        _config: Config,
+        config: Config,
     ) -> Result<f32, crate::Error> {
         let dohr_time = Self::dohr(time, location)?;
 
-        let angle = 90.83333; // constants
-        Ok(dohr_time + Self::time_for_angle(angle, time, location)?)
+        let angle = Self::sunset_angle(location, config);
+        Ok(dohr_time + Self::hour_angle(angle, time, location, config)?)
     }
     /// Get the Ishaa time
     fn ishaa(
@@ -172,19 +412,31 @@ impl PrayerTimes {
         location: Location,
         config: Config,
     ) -> Result<f32, crate::Error> {
+        if config.method == Method::MoonsightingCommittee {
+            return Self::seasonal_twilight(Prayer::Ishaa, time, location, config);
+        }
         let dohr_time = Self::dohr(time, location)?;
 
         // checking one of `all_year` or `ramadan` is enough
         // because if set, none of them would be 0.0
         if config.isha_interval.all_year > 0.0 {
-            let is_ramadan = HijriDate::from_gregorian(time.date(), 0).month == 9;
+            let correction = config.hijri_correction;
+            let hijri = match config
+                .hijri_calendar
+                .from_gregorian(time.date(), correction)
+            {
+                Ok(hijri) => hijri,
+                // Outside the Umm al-Qura table, Ramadan is found with the tabular calendar
+                Err(_) => Calendar::default().from_gregorian(time.date(), correction)?,
+            };
+            let is_ramadan = hijri.month == 9;
             let time_after_maghreb = if is_ramadan {
                 config.isha_interval.ramdan / 60.0
             } else {
                 config.isha_interval.all_year / 60.0
             };
-            let angle = 90.83333; //  Constants (maghreb angle)
-            Ok(time_after_maghreb + dohr_time + Self::time_for_angle(angle, time, location)?)
+            let angle = Self::sunset_angle(location, config);
+            Ok(time_after_maghreb + dohr_time + Self::hour_angle(angle, time, location, config)?)
         } else {
             // NOTE (upstream) why still need FixedInterval comparison?
             // let angle = if config.method == Method::FixedInterval {
@@ -192,8 +444,9 @@ impl PrayerTimes {
             // } else {
             //     config.ishaa_angle + 90.0
             // };
-            let angle = config.ishaa_angle + 90.0;
-            Ok(dohr_time + Self::time_for_angle(angle, time, location)?)
+            let angle = Self::twilight_angle(config.ishaa_angle, location, config);
+            let ishaa_time = dohr_time + Self::hour_angle(angle, time, location, config)?;
+            Self::night_portion(Prayer::Ishaa, ishaa_time, time, location, config)
         }
     }
     /// Get the Fajr time
@@ -202,6 +455,9 @@ impl PrayerTimes {
         location: Location,
         config: Config,
     ) -> Result<f32, crate::Error> {
+        if config.method == Method::MoonsightingCommittee {
+            return Self::seasonal_twilight(Prayer::Fajr, time, location, config);
+        }
         let dohr_time = Self::dohr(time, location)?;
         // NOTE (upstream) wrong if-else?
         // let angle = if config.method == Method::FixedInterval {
@@ -209,19 +465,146 @@ impl PrayerTimes {
         // } else {
         //     config.fajr_angle
         // };
-        let angle = config.fajr_angle + 90.0;
-        Ok(dohr_time - Self::time_for_angle(angle, time, location)?)
+        let angle = Self::twilight_angle(config.fajr_angle, location, config);
+        let fajr_time = dohr_time - Self::hour_angle(angle, time, location, config)?;
+        Self::night_portion(Prayer::Fajr, fajr_time, time, location, config)
+    }
+    /// Fajr and Ishaa of the Moonsighting Committee, minutes from sunrise and sunset
+    /// that depend on the season, or a seventh of the night above 55°
+    fn seasonal_twilight(
+        prayer: Prayer,
+        time: civil::DateTime,
+        location: Location,
+        config: Config,
+    ) -> Result<f32, crate::Error> {
+        let sherook_time = Self::sherook(time, location, config)?;
+        let sunset_time = Self::sunset(time, location, config)?;
+        let twilight = if location.latitude.abs() >= 55.0 {
+            (24.0 - (sunset_time - sherook_time)) / 7.0
+        } else {
+            let date = time.date();
+            let minutes = match prayer {
+                Prayer::Ishaa => {
+                    moonsighting::evening_twilight(location.latitude, date, config.shafaq)
+                }
+                _ => moonsighting::morning_twilight(location.latitude, date),
+            };
+            minutes / 60.0
+        };
+        match prayer {
+            Prayer::Ishaa => Ok(sunset_time + twilight),
+            _ => Ok(sherook_time - twilight),
+        }
+    }
+    /// Get the Imsak time
+    fn imsak(
+        time: civil::DateTime,
+        location: Location,
+        config: Config,
+    ) -> Result<f32, crate::Error> {
+        match config.imsak {
+            Imsak::Minutes(minutes) => Ok(Self::fajr(time, location, config)? - minutes / 60.0),
+            Imsak::Angle(angle) => {
+                let dohr_time = Self::dohr(time, location)?;
+                let angle = Self::twilight_angle(angle, location, config);
+                let imsak_time = dohr_time - Self::hour_angle(angle, time, location, config)?;
+                Self::night_portion(Prayer::Imsak, imsak_time, time, location, config)
+            }
+        }
     }
     /// Get the Sherook time
     fn sherook(
         time: civil::DateTime,
         location: Location,
-        _config: Config,
+        config: Config,
     ) -> Result<f32, crate::Error> {
         let dohr_time = Self::dohr(time, location)?;
 
-        let angle = 90.83333;
-        Ok(dohr_time - Self::time_for_angle(angle, time, location)?)
+        let angle = Self::sunset_angle(location, config);
+        Ok(dohr_time - Self::hour_angle(angle, time, location, config)?)
+    }
+    /// The bound of a makruh window that is not a prayer time, with its offset in minutes.
+    /// Minutes follow the offset of the prayer they are counted from.
+    fn makruh_bound(
+        makruh: Makruh,
+        extent: Extent,
+        time: civil::DateTime,
+        location: Location,
+        config: Config,
+    ) -> Result<(f32, f32), crate::Error> {
+        let offsets = config.offsets;
+        match (makruh, extent) {
+            (Makruh::Sunrise, Extent::Minutes(minutes)) => Ok((
+                Self::sherook(time, location, config)? + minutes / 60.0,
+                offsets.sherook,
+            )),
+            (Makruh::Zawal, Extent::Minutes(minutes)) => {
+                Ok((Self::dohr(time, location)? - minutes / 60.0, offsets.dohr))
+            }
+            (Makruh::Sunset, Extent::Minutes(minutes)) => Ok((
+                Self::maghreb(time, location, config)? - minutes / 60.0,
+                offsets.maghreb,
+            )),
+            (Makruh::Sunrise, Extent::Altitude(altitude)) => {
+                let hours = Self::time_for_angle(90.0 - altitude, time, location)?;
+                Ok((Self::dohr(time, location)? - hours, 0.0))
+            }
+            (Makruh::Zawal, Extent::Altitude(altitude)) => {
+                // angle of the sun from the zenith at Dohr
+                let zenith = (location.latitude - Self::sun_declination(time)?).abs();
+                let hours = Self::time_for_angle(zenith + altitude, time, location)?;
+                Ok((Self::dohr(time, location)? - hours, 0.0))
+            }
+            (Makruh::Sunset, Extent::Altitude(altitude)) => {
+                let hours = Self::time_for_angle(90.0 - altitude, time, location)?;
+                Ok((Self::dohr(time, location)? + hours, 0.0))
+            }
+            (makruh, Extent::DayFraction(fraction)) => {
+                let sherook = Self::sherook(time, location, config)?;
+                let maghreb = Self::maghreb(time, location, config)?;
+                let hours = fraction * (maghreb - sherook);
+                Ok(match makruh {
+                    Makruh::Sunrise => (sherook + hours, offsets.sherook),
+                    Makruh::Zawal => (Self::dohr(time, location)? - hours, offsets.dohr),
+                    Makruh::Sunset => (maghreb - hours, offsets.maghreb),
+                })
+            }
+        }
+    }
+    /// Limit Imsak, Fajr and Ishaa to a portion of the night, according to the high latitude rule
+    fn night_portion(
+        prayer: Prayer,
+        val: f32,
+        time: civil::DateTime,
+        location: Location,
+        config: Config,
+    ) -> Result<f32, crate::Error> {
+        let angle = match (prayer, config.imsak) {
+            (Prayer::Ishaa, _) => config.ishaa_angle,
+            (Prayer::Imsak, Imsak::Angle(angle)) => angle,
+            _ => config.fajr_angle,
+        };
+        let Some(portion) = config
+            .high_latitude_rule
+            .and_then(|rule| rule.portion(angle))
+        else {
+            return Ok(val);
+        };
+
+        let sherook_time = Self::sherook(time, location, config)?;
+        let sunset_time = Self::sunset(time, location, config)?;
+        let night = 24.0 - (sunset_time - sherook_time);
+        let portion = portion * night;
+
+        match prayer {
+            Prayer::Ishaa if val.is_nan() || val - sunset_time > portion => {
+                Ok(sunset_time + portion)
+            }
+            Prayer::Imsak | Prayer::Fajr if val.is_nan() || sherook_time - val > portion => {
+                Ok(sherook_time - portion)
+            }
+            _ => Ok(val),
+        }
     }
     /// Get the third of night
     fn first_third_of_night(
@@ -229,20 +612,16 @@ impl PrayerTimes {
         location: Location,
         config: Config,
     ) -> Result<f32, crate::Error> {
-        let maghreb_time = Self::maghreb(time, location, config)?;
-        let fajr_time = Self::fajr(time, location, config)?;
-        Ok(maghreb_time + (24.0 - (maghreb_time - fajr_time)) / 3.0)
+        Self::night_fraction(1.0 / 3.0, time, location, config)
     }
-    /// Midnight is the exact time between sunrise (Shorook) and sunset (Maghreb),
+    /// Midnight is the middle of the night, measured according to the midnight method,
     /// It defines usually the end of Ishaa time
     fn midnight(
         time: civil::DateTime,
         location: Location,
         config: Config,
     ) -> Result<f32, crate::Error> {
-        let maghreb_time = Self::maghreb(time, location, config)?;
-        let fajr_time = Self::fajr(time, location, config)?;
-        Ok(maghreb_time + (24.0 - (maghreb_time - fajr_time)) / 2.0)
+        Self::night_fraction(1.0 / 2.0, time, location, config)
     }
     /// Qiyam time starts after Ishaa directly, however, the best time for Qiyam is the last third of night
     fn last_third_of_night(
@@ -250,42 +629,75 @@ impl PrayerTimes {
         location: Location,
         config: Config,
     ) -> Result<f32, crate::Error> {
-        let maghreb_time = Self::maghreb(time, location, config)?;
-
-        let fajr_time = Self::fajr(time, location, config)?;
-        Ok(maghreb_time + (2.0 * (24.0 - (maghreb_time - fajr_time)) / 3.0))
+        Self::night_fraction(2.0 / 3.0, time, location, config)
     }
-    /// Convert a decimal value (in hours) to time object
+    /// The time after the given fraction of the night,
+    /// the night ends with tomorrow's Fajr or Sherook
+    fn night_fraction(
+        fraction: f32,
+        time: civil::DateTime,
+        location: Location,
+        config: Config,
+    ) -> Result<f32, crate::Error> {
+        let tomorrow = time + 1.days();
+        let (start, end) = match config.midnight_method {
+            MidnightMethod::MaghrebToFajr => (
+                Self::maghreb(time, location, config)?,
+                Self::fajr(tomorrow, location, config)?,
+            ),
+            MidnightMethod::MaghrebToSherook => (
+                Self::maghreb(time, location, config)?,
+                Self::sherook(tomorrow, location, config)?,
+            ),
+            MidnightMethod::Jafari => (
+                Self::sunset(time, location, config)?,
+                Self::fajr(tomorrow, location, config)?,
+            ),
+        };
+        Ok(start + fraction * (end + 24.0 - start))
+    }
+    /// Convert a decimal value (in UTC hours) to an instant, `shift` is in seconds.
+    /// Hours are counted from midnight UTC of the day of the local noon, so that
+    /// zones far from their meridian (e.g. Pacific/Apia) keep the times on the local date.
     fn hours_to_time(
         time: civil::DateTime,
         val: f32,
         shift: f32,
-        config: Config,
-    ) -> civil::DateTime {
-        let is_summer = i32::from(config.is_summer);
+        rounding: Rounding,
+        timezone: &TimeZone,
+    ) -> Result<Timestamp, crate::Error> {
+        // The sun never reaches the angle, and no high latitude rule could place the time
+        if val.is_nan() {
+            return Err(crate::Error::InvalidTime);
+        }
         let hour = val + (shift / 3600.0);
-        let minute = (hour - (hour).floor()) * 60.0;
-        let second = (minute - (minute).floor()) * 60.0;
-        let hour = (hour + is_summer as f32).floor() % 24.0;
-        let time = civil::date(time.year(), time.month(), time.day()).at(
-            hour as i8,
-            minute as i8,
-            second as i8,
-            0,
-        );
-        time.round(Unit::Minute).unwrap()
+        let seconds = (hour * 3600.0).floor() as i64;
+        let noon = time.date().at(12, 0, 0, 0).to_zoned(timezone.clone())?;
+        let day = noon.timestamp().to_zoned(TimeZone::UTC).date();
+        let midnight = day.to_zoned(TimeZone::UTC)?.timestamp();
+        let instant = Timestamp::from_second(midnight.as_second() + seconds)?;
+        let mode = match rounding {
+            Rounding::Nearest => Some(RoundMode::HalfExpand),
+            Rounding::Up | Rounding::Safe => Some(RoundMode::Ceil),
+            Rounding::Down => Some(RoundMode::Floor),
+            Rounding::None => None,
+        };
+        match mode {
+            Some(mode) => {
+                Ok(instant.round(TimestampRound::new().smallest(Unit::Minute).mode(mode))?)
+            }
+            None => Ok(instant),
+        }
     }
-    fn longitude_difference(location: Location) -> Result<f32, crate::Error> {
-        let offset = jiff::Zoned::now().offset().seconds();
-        let offset_hour = offset.seconds().total(Unit::Hour)?;
-        let middle_longitude = offset_hour as f32 * 15.0;
-        Ok((middle_longitude - location.longitude) / 15.0)
+    /// Prayer times are calculated in UTC, the time zone is only applied when converting them
+    fn longitude_difference(location: Location) -> f32 {
+        -location.longitude / 15.0
     }
-    /// Get the angle angle for asr (according to chosen madhab)
+    /// Get the angle for asr (according to the shadow factor of the chosen madhab)
     fn asr_angle(
+        shadow: f32,
         time: civil::DateTime,
         location: Location,
-        config: Config,
     ) -> Result<f32, crate::Error> {
         let delta = Self::sun_declination(time)?;
         let x = cal::dsin(location.latitude).mul_add(
@@ -293,9 +705,44 @@ impl PrayerTimes {
             cal::dcos(location.latitude) * cal::dcos(delta),
         );
         let a = (x / (-x).mul_add(x, 1.0).sqrt()).atan();
-        let x = config.madhab as i32 as f32 + (1.0 / (a).tan());
+        let x = shadow + (1.0 / (a).tan());
         Ok(90.0 - (180.0 / PI) * 2.0_f32.mul_add((1.0_f32).atan(), (x).atan()))
     }
+    /// Angle of the sun at Sherook and Maghreb, when the upper limb of the sun touches the horizon.
+    /// The horizon is lower than 90° for an observer above the ground (dip of the horizon).
+    fn sunset_angle(location: Location, config: Config) -> f32 {
+        let semidiameter = 16.0 / 60.0;
+        90.0 + semidiameter + config.refraction.horizon() + Self::dip(location)
+    }
+    /// Angle of the sun at a twilight depression, below the same horizon as `sunset_angle`.
+    /// The refraction only lifts a sun that is less than its horizon refraction below the horizon.
+    fn twilight_angle(depression: f32, location: Location, config: Config) -> f32 {
+        let refraction = (config.refraction.horizon() - depression).max(0.0);
+        90.0 + depression + refraction + Self::dip(location)
+    }
+    /// Dip of the horizon, in degrees, for the elevation in metres
+    fn dip(location: Location) -> f32 {
+        0.0347 * location.elevation.max(0.0).sqrt()
+    }
+    /// Same as `time_for_angle`, but use the nearest latitude if the sun never reaches the angle
+    fn hour_angle(
+        angle: f32,
+        time: civil::DateTime,
+        location: Location,
+        config: Config,
+    ) -> Result<f32, crate::Error> {
+        let hours = Self::time_for_angle(angle, time, location)?;
+        match config.high_latitude_rule {
+            Some(HighLatitudeRule::NearestLatitude(latitude)) if hours.is_nan() => {
+                let nearest = Location {
+                    latitude: location.latitude.clamp(-latitude, latitude),
+                    ..location
+                };
+                Self::time_for_angle(angle, time, nearest)
+            }
+            _ => Ok(hours),
+        }
+    }
     /// Get Times for "Fajr, Sherook, Asr, Maghreb, ishaa"
     fn time_for_angle(
         angle: f32,
@@ -310,13 +757,7 @@ impl PrayerTimes {
     /// Get sun declination
     fn sun_declination(time: civil::DateTime) -> Result<f32, crate::Error> {
         let julian_date = cal::gregorian_to_julian(time.date());
-        let n = julian_date - 2_451_544.5;
-        let epsilon = 23.44 - (0.000_000_4 * n);
-        let l = 0.985_647_4_f32.mul_add(n, 280.466);
-        let g = 0.985_600_3_f32.mul_add(n, 357.528);
-        let lambda = 0.02_f32.mul_add(cal::dsin(2.0 * g), 1.915_f32.mul_add(cal::dsin(g), l));
-        let x = cal::dsin(epsilon) * cal::dsin(lambda);
-        Ok((180.0 / (4.0 * (1.0_f32).atan())) * (x / (-x).mul_add(x, 1.0).sqrt()).atan())
+        Ok(cal::sun_declination(julian_date))
     }
     /// Remaining time to next prayer
     pub fn time_remaining(&self) -> (u32, u32) {
@@ -337,65 +778,159 @@ impl PrayerTimes {
 
         (hours, minutes)
     }
-    /// Get next prayer
+    /// Get next prayer, Jumuah included
     pub fn next(&self) -> Prayer {
-        match self.current() {
-            Prayer::Fajr => Prayer::Sherook,
-            Prayer::Sherook => Prayer::Dohr,
-            Prayer::Dohr => Prayer::Asr,
-            Prayer::Asr => Prayer::Maghreb,
-            Prayer::Maghreb => Prayer::Ishaa,
-            Prayer::Ishaa => {
-                if self.is_after_midnight() {
-                    Prayer::Fajr
-                } else {
-                    Prayer::FajrTomorrow
-                }
-            }
-            Prayer::FajrTomorrow => Prayer::Fajr,
+        let next = self.next_after(self.current(), self.is_after_midnight());
+        Self::jumuah_before(next, self.now(), self.jumuah, self.time(next))
+    }
+    /// Helper function for `next`
+    fn next_after(&self, current: Prayer, is_after_midnight: bool) -> Prayer {
+        let events = self.events();
+        match current {
+            Prayer::Ishaa if !is_after_midnight => Prayer::FajrTomorrow,
+            Prayer::Ishaa | Prayer::FajrTomorrow => events[0],
+            _ => events
+                .iter()
+                .skip_while(|&&prayer| prayer != current)
+                .nth(1)
+                .copied()
+                .unwrap_or(Prayer::Fajr),
         }
     }
-    /// Get prayer's time
+    /// Jumuah is next when it is still to come, before the next prayer
+    fn jumuah_before<T: PartialOrd>(
+        next: Prayer,
+        now: T,
+        jumuah: Option<T>,
+        next_time: T,
+    ) -> Prayer {
+        match jumuah {
+            Some(jumuah) if now < jumuah && jumuah < next_time => Prayer::Jumuah,
+            _ => next,
+        }
+    }
+    /// Today's prayers in chronological order, with the enabled optional events.
+    /// Jumuah is not one of them, Dohr keeps its window on Fridays.
+    fn events(&self) -> Vec<Prayer> {
+        let mut events = Vec::new();
+        let optional = self.config.optional_events;
+        if optional.imsak {
+            events.push(Prayer::Imsak);
+        }
+        events.extend([Prayer::Fajr, Prayer::Sherook]);
+        if optional.ishraq {
+            events.push(Prayer::Ishraq);
+        }
+        if optional.duha {
+            events.push(Prayer::Duha);
+        }
+        events.extend([Prayer::Dohr, Prayer::Asr, Prayer::Maghreb, Prayer::Ishaa]);
+        events
+    }
+    /// Whether the prayer's time was moved by the high latitude rule
+    pub fn is_adjusted(&self, prayer: Prayer) -> bool {
+        self.adjusted.contains(&prayer)
+    }
+    /// Get the makruh windows of the day
+    pub fn makruh_windows(&self) -> Vec<(Makruh, Range<civil::DateTime>)> {
+        self.makruh.clone()
+    }
+    /// Get the makruh window at `time`, if any
+    pub fn makruh_at(&self, time: civil::DateTime) -> Option<Makruh> {
+        self.makruh
+            .iter()
+            .find(|(_, range)| range.contains(&time))
+            .map(|(makruh, _)| *makruh)
+    }
+    /// Get the start and end of a prayer, Imsak, Sherook and Fajr of tomorrow have no window,
+    /// and Jumuah has none on the other days
+    pub fn window(&self, prayer: Prayer) -> Option<PrayerWindow> {
+        let window = |end, preferred_end| {
+            Some(PrayerWindow {
+                start: self.time(prayer),
+                end,
+                preferred_end,
+            })
+        };
+        match prayer {
+            Prayer::Fajr => window(self.sherook, None),
+            // Voluntary prayers end before zawal
+            Prayer::Ishraq | Prayer::Duha => window(self.zawal, None),
+            Prayer::Dohr => window(self.asr, None),
+            Prayer::Jumuah if self.jumuah.is_some() => window(self.asr, None),
+            Prayer::Asr => window(self.maghreb, self.asr_preferred_end),
+            Prayer::Maghreb => window(self.ishaa, None),
+            Prayer::Ishaa => match self.config.ishaa_end {
+                IshaaEnd::Midnight => window(self.midnight, None),
+                IshaaEnd::Fajr => window(self.fajr_tomorrow, Some(self.midnight)),
+            },
+            Prayer::Imsak | Prayer::Sherook | Prayer::Jumuah | Prayer::FajrTomorrow => None,
+        }
+    }
+    /// Get the name of the prayer on the date of these times, Dohr is Jumua when Jumuah is at Dohr
+    pub fn name_of(&self, prayer: Prayer) -> String {
+        match prayer {
+            Prayer::Dohr if self.jumuah == Some(self.dohr) => Prayer::Jumuah.name(),
+            _ => prayer.name(),
+        }
+    }
+    /// Get prayer's time, Jumuah is at Dohr on the other days
     pub fn time(&self, prayer: Prayer) -> civil::DateTime {
         match prayer {
+            Prayer::Imsak => self.imsak,
             Prayer::Fajr => self.fajr,
             Prayer::Sherook => self.sherook,
+            Prayer::Ishraq => self.ishraq,
+            Prayer::Duha => self.duha,
             Prayer::Dohr => self.dohr,
+            Prayer::Jumuah => self.jumuah.unwrap_or(self.dohr),
             Prayer::Asr => self.asr,
             Prayer::Maghreb => self.maghreb,
             Prayer::Ishaa => self.ishaa,
             Prayer::FajrTomorrow => self.fajr_tomorrow,
         }
     }
+    /// Get prayer's instant, like `time`
+    fn instant(&self, prayer: Prayer) -> Timestamp {
+        let instants = &self.instants;
+        match prayer {
+            Prayer::Imsak => instants.imsak,
+            Prayer::Fajr => instants.fajr,
+            Prayer::Sherook => instants.sherook,
+            Prayer::Ishraq => instants.ishraq,
+            Prayer::Duha => instants.duha,
+            Prayer::Dohr => instants.dohr,
+            Prayer::Jumuah => instants.jumuah.unwrap_or(instants.dohr),
+            Prayer::Asr => instants.asr,
+            Prayer::Maghreb => instants.maghreb,
+            Prayer::Ishaa => instants.ishaa,
+            Prayer::FajrTomorrow => instants.fajr_tomorrow,
+        }
+    }
     /// Get current prayer
     pub fn current(&self) -> Prayer {
-        let now = match self.custom_time {
-            None => time::now().into(),
-            Some(custom) => custom,
-        };
-        self.current_time(now).expect("Out of bounds")
+        self.current_time(self.now()).expect("Out of bounds")
     }
     /// Helper function for `current`
     fn current_time(&self, time: civil::DateTime) -> Option<Prayer> {
+        self.current_at(time, |prayer| self.time(prayer))
+    }
+    /// Get the prayer at `time`, `time_of` gives each prayer's time in the same representation
+    fn current_at<T: PartialOrd>(&self, time: T, time_of: impl Fn(Prayer) -> T) -> Option<Prayer> {
         let mut current_prayer: Option<Prayer> = None;
 
-        let ranges = vec![
-            (Prayer::Fajr, self.fajr..self.sherook),
-            (Prayer::Sherook, self.sherook..self.dohr),
-            (Prayer::Dohr, self.dohr..self.asr),
-            (Prayer::Asr, self.asr..self.maghreb),
-            (Prayer::Maghreb, self.maghreb..self.ishaa),
-            (Prayer::Ishaa, self.ishaa..self.fajr_tomorrow),
-        ];
-        for (prayer, range) in ranges {
-            if range.contains(&time) {
-                current_prayer = Some(prayer);
+        // Each event lasts until the next one, Ishaa until Fajr of tomorrow
+        let mut events = self.events();
+        events.push(Prayer::FajrTomorrow);
+        for pair in events.windows(2) {
+            if (time_of(pair[0])..time_of(pair[1])).contains(&time) {
+                current_prayer = Some(pair[0]);
             }
         }
 
         // Special case for time after 00:00
         // It never get any matching prayer in the iteration above
-        if current_prayer.is_none() && time < self.fajr {
+        if current_prayer.is_none() && time < time_of(events[0]) {
             current_prayer = Some(Prayer::Ishaa)
         }
 
@@ -409,16 +944,126 @@ impl PrayerTimes {
     /// It can be real current time or user specified time
     fn now(&self) -> civil::DateTime {
         match self.custom_time {
-            None => time::now().into(),
+            None => time::now().with_time_zone(self.timezone.clone()).datetime(),
             Some(custom) => custom,
         }
     }
 }
 
+/// Prayer times as instants in the time zone of the schedule
+#[derive(Debug, Clone)]
+pub struct ZonedPrayerTimes {
+    times: PrayerTimes,
+}
+
+impl ZonedPrayerTimes {
+    pub const fn new(times: PrayerTimes) -> Self {
+        Self { times }
+    }
+    /// The underlying civil prayer times
+    pub const fn civil(&self) -> &PrayerTimes {
+        &self.times
+    }
+    pub fn imsak(&self) -> Zoned {
+        self.zoned(self.times.instants.imsak)
+    }
+    pub fn fajr(&self) -> Zoned {
+        self.zoned(self.times.instants.fajr)
+    }
+    pub fn sherook(&self) -> Zoned {
+        self.zoned(self.times.instants.sherook)
+    }
+    pub fn ishraq(&self) -> Zoned {
+        self.zoned(self.times.instants.ishraq)
+    }
+    pub fn duha(&self) -> Zoned {
+        self.zoned(self.times.instants.duha)
+    }
+    pub fn dohr(&self) -> Zoned {
+        self.zoned(self.times.instants.dohr)
+    }
+    pub fn jumuah(&self) -> Option<Zoned> {
+        self.times.instants.jumuah.map(|jumuah| self.zoned(jumuah))
+    }
+    pub fn asr(&self) -> Zoned {
+        self.zoned(self.times.instants.asr)
+    }
+    pub fn maghreb(&self) -> Zoned {
+        self.zoned(self.times.instants.maghreb)
+    }
+    pub fn ishaa(&self) -> Zoned {
+        self.zoned(self.times.instants.ishaa)
+    }
+    pub fn fajr_tomorrow(&self) -> Zoned {
+        self.zoned(self.times.instants.fajr_tomorrow)
+    }
+    pub fn first_third_of_night(&self) -> Zoned {
+        self.zoned(self.times.instants.first_third_of_night)
+    }
+    pub fn midnight(&self) -> Zoned {
+        self.zoned(self.times.instants.midnight)
+    }
+    pub fn last_third_of_night(&self) -> Zoned {
+        self.zoned(self.times.instants.last_third_of_night)
+    }
+    /// Get prayer's time, Jumuah is at Dohr on the other days
+    pub fn time(&self, prayer: Prayer) -> Zoned {
+        self.zoned(self.timestamp(prayer))
+    }
+    /// Get prayer's instant
+    pub fn timestamp(&self, prayer: Prayer) -> Timestamp {
+        self.times.instant(prayer)
+    }
+    /// Get current prayer
+    pub fn current(&self) -> Prayer {
+        let now = self.now();
+        self.times
+            .current_at(now, |prayer| self.timestamp(prayer))
+            .expect("Out of bounds")
+    }
+    /// Get next prayer
+    pub fn next(&self) -> Prayer {
+        let is_after_midnight = self.now() < self.times.instants.fajr;
+        let next = self.times.next_after(self.current(), is_after_midnight);
+        let jumuah = self.times.instants.jumuah;
+        PrayerTimes::jumuah_before(next, self.now(), jumuah, self.timestamp(next))
+    }
+    /// Remaining time to next prayer
+    pub fn time_remaining(&self) -> SignedDuration {
+        self.timestamp(self.next()).duration_since(self.now())
+    }
+    /// The instant in the time zone of the schedule
+    fn zoned(&self, instant: Timestamp) -> Zoned {
+        instant.to_zoned(self.times.timezone.clone())
+    }
+    /// Get the current instant
+    /// It can be real current time or user specified time
+    fn now(&self) -> Timestamp {
+        match self.times.custom_time {
+            None => Timestamp::now(),
+            Some(custom) => self
+                .times
+                .timezone
+                .to_ambiguous_zoned(custom)
+                .compatible()
+                .expect("Out of bounds")
+                .timestamp(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::salah::{madhab::Madhab, method::Method};
+    use crate::hijri::HijriDate;
+    use crate::salah::{
+        config::{Offsets, OptionalEvents},
+        madhab::Madhab,
+        makruh::{Extent, MakruhExtents},
+        method::Method,
+        moonsighting::Shafaq,
+        refraction::Refraction,
+    };
 
     fn date() -> civil::Date {
         civil::date(2025, 3, 12)
@@ -433,8 +1078,11 @@ mod tests {
         // JadwalSholat is also using Shafi as the madhab, `20.0 deg` for fajs angle, and `18.0 deg` for Ishaa angle.
         Config::new().with(Method::Singapore, Madhab::Shafi)
     }
+    fn timezone() -> Result<TimeZone, crate::Error> {
+        Ok(TimeZone::get("Asia/Jakarta")?)
+    }
     fn prayer_times_on() -> Result<PrayerTimes, crate::Error> {
-        let prayer_times = PrayerSchedule::new(city())
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
             .on(date())?
             .with_config(config())
             .calculate()?;
@@ -442,7 +1090,7 @@ mod tests {
     }
     fn prayer_times_at(time: (i8, i8, i8)) -> Result<PrayerTimes, crate::Error> {
         let time = date().at(time.0, time.1, time.2, 0);
-        let prayer_times = PrayerSchedule::new(city())
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
             .at(time)
             .with_config(config())
             .calculate()?;
@@ -582,7 +1230,7 @@ mod tests {
     #[test]
     fn before_midnight() -> Result<(), crate::Error> {
         let time = date().at(20, 0, 0, 0);
-        let prayer_times = PrayerSchedule::new(city())
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
             .at(time)
             .with_config(config())
             .calculate()?;
@@ -594,7 +1242,7 @@ mod tests {
     #[test]
     fn after_midnight() -> Result<(), crate::Error> {
         let time = civil::date(2023, 8, 31).at(2, 0, 0, 0);
-        let prayer_times = PrayerSchedule::new(city())
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
             .at(time)
             .with_config(config())
             .calculate()?;
@@ -603,4 +1251,672 @@ mod tests {
         assert_eq!(prayer_times.time_remaining(), (2, 37));
         Ok(())
     }
+    #[test]
+    fn daylight_saving_time() -> Result<(), crate::Error> {
+        let new_york = Location::new(40.71, -74.01);
+        let timezone = TimeZone::get("America/New_York")?;
+
+        // Eastern Standard Time (UTC-5)
+        let prayer_times = PrayerSchedule::new(new_york, timezone.clone())
+            .on(civil::date(2025, 1, 15))?
+            .calculate()?;
+        assert_eq!(prayer_times.dohr, civil::date(2025, 1, 15).at(12, 5, 0, 0));
+
+        // Eastern Daylight Time (UTC-4)
+        let prayer_times = PrayerSchedule::new(new_york, timezone)
+            .on(civil::date(2025, 7, 15))?
+            .calculate()?;
+        assert_eq!(prayer_times.dohr, civil::date(2025, 7, 15).at(13, 2, 0, 0));
+        Ok(())
+    }
+    #[test]
+    fn far_from_meridian() -> Result<(), crate::Error> {
+        // UTC+13 and UTC+14, while the longitudes are about UTC-11 and UTC-10
+        for (location, timezone) in [
+            (Location::new(-13.83, -171.76), "Pacific/Apia"),
+            (Location::new(1.87, -157.4), "Pacific/Kiritimati"),
+        ] {
+            let prayer_times = PrayerSchedule::new(location, TimeZone::get(timezone)?)
+                .on(date())?
+                .calculate()?;
+            for prayer in [
+                Prayer::Fajr,
+                Prayer::Sherook,
+                Prayer::Dohr,
+                Prayer::Asr,
+                Prayer::Maghreb,
+                Prayer::Ishaa,
+            ] {
+                assert_eq!(prayer_times.time(prayer).date(), date(), "{timezone}");
+            }
+            assert_eq!(prayer_times.fajr_tomorrow.date(), date().tomorrow()?);
+        }
+        Ok(())
+    }
+    #[test]
+    fn times_in_utc() -> Result<(), crate::Error> {
+        let prayer_times = PrayerSchedule::new(city(), TimeZone::UTC)
+            .on(date())?
+            .with_config(config())
+            .calculate()?;
+        assert_eq!(prayer_times.dohr, expected_time(5, 4, 0));
+        Ok(())
+    }
+    #[test]
+    fn zoned_prayer_times() -> Result<(), crate::Error> {
+        let time = date().at(20, 0, 0, 0);
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .at(time)
+            .with_config(config())
+            .calculate_zoned()?;
+
+        let fajr = prayer_times.fajr();
+        assert_eq!(fajr.datetime(), expected_time(4, 42, 0));
+        assert_eq!(fajr.time_zone(), &timezone()?);
+        assert_eq!(
+            prayer_times.timestamp(Prayer::Fajr),
+            "2025-03-11T21:42:00Z".parse()?
+        );
+
+        assert_eq!(prayer_times.current(), Prayer::Ishaa);
+        assert_eq!(prayer_times.next(), Prayer::FajrTomorrow);
+        assert_eq!(
+            prayer_times.time_remaining(),
+            SignedDuration::from_mins(8 * 60 + 42)
+        );
+        Ok(())
+    }
+    #[test]
+    fn zoned_after_midnight() -> Result<(), crate::Error> {
+        let time = date().at(1, 0, 0, 0);
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .at(time)
+            .with_config(config())
+            .calculate_zoned()?;
+
+        assert_eq!(prayer_times.current(), Prayer::Ishaa);
+        assert_eq!(prayer_times.next(), Prayer::Fajr);
+        assert_eq!(
+            prayer_times.time_remaining(),
+            SignedDuration::from_mins(3 * 60 + 42)
+        );
+        Ok(())
+    }
+    #[test]
+    fn zoned_in_repeated_hour() -> Result<(), crate::Error> {
+        // The clocks go back from 03:00 to 02:00 on the night of 25 to 26 October,
+        // the longitude puts the last third of the night in the repeated hour
+        let prayer_times =
+            PrayerSchedule::new(Location::new(48.85, -5.0), TimeZone::get("Europe/Paris")?)
+                .on(civil::date(2025, 10, 25))?
+                .calculate_zoned()?;
+        let last_third_of_night = prayer_times.last_third_of_night();
+        assert_eq!(
+            last_third_of_night.datetime(),
+            civil::date(2025, 10, 26).at(2, 12, 0, 0)
+        );
+        assert_eq!(
+            last_third_of_night.timestamp(),
+            "2025-10-26T01:12:00Z".parse()?
+        );
+        Ok(())
+    }
+    fn london_on_summer_solstice(config: Config) -> Result<PrayerTimes, crate::Error> {
+        PrayerSchedule::new(
+            Location::new(51.5074, -0.1278),
+            TimeZone::get("Europe/London")?,
+        )
+        .on(civil::date(2025, 6, 21))?
+        .with_config(config)
+        .calculate()
+    }
+    #[test]
+    fn high_latitude_without_rule() {
+        // The sun never goes 17° below the horizon in London in June
+        let config = Config::new().with(Method::MuslimWorldLeague, Madhab::Shafi);
+        let err = london_on_summer_solstice(config).unwrap_err();
+        assert_eq!(err, crate::Error::InvalidTime);
+    }
+    #[test]
+    fn high_latitude_rules() -> Result<(), crate::Error> {
+        let expected = |hour, minute| civil::date(2025, 6, 21).at(hour, minute, 0, 0);
+        let config = Config::new().with(Method::MuslimWorldLeague, Madhab::Shafi);
+
+        let config = config.high_latitude_rule(HighLatitudeRule::MiddleOfTheNight);
+        let prayer_times = london_on_summer_solstice(config)?;
+        assert_eq!(prayer_times.sherook, expected(4, 43));
+        assert_eq!(prayer_times.maghreb, expected(21, 21));
+        assert_eq!(prayer_times.fajr, expected(1, 2));
+        assert_eq!(prayer_times.ishaa, civil::date(2025, 6, 22).at(1, 2, 0, 0));
+        assert!(prayer_times.is_adjusted(Prayer::Fajr));
+        assert!(prayer_times.is_adjusted(Prayer::Ishaa));
+        assert!(!prayer_times.is_adjusted(Prayer::Maghreb));
+
+        let config = config.high_latitude_rule(HighLatitudeRule::SeventhOfTheNight);
+        let prayer_times = london_on_summer_solstice(config)?;
+        assert_eq!(prayer_times.fajr, expected(3, 40));
+        assert_eq!(prayer_times.ishaa, expected(22, 24));
+
+        let config = config.high_latitude_rule(HighLatitudeRule::TwilightAngle);
+        let prayer_times = london_on_summer_solstice(config)?;
+        assert_eq!(prayer_times.fajr, expected(2, 31));
+        assert_eq!(prayer_times.ishaa, expected(23, 27));
+
+        let config = config.high_latitude_rule(HighLatitudeRule::NearestLatitude(48.5));
+        let prayer_times = london_on_summer_solstice(config)?;
+        assert_eq!(prayer_times.fajr, expected(1, 16));
+        assert_eq!(prayer_times.ishaa, civil::date(2025, 6, 22).at(0, 7, 0, 0));
+        assert!(prayer_times.is_adjusted(Prayer::Fajr));
+        Ok(())
+    }
+    #[test]
+    fn low_latitude_is_not_adjusted() -> Result<(), crate::Error> {
+        let config = config().high_latitude_rule(HighLatitudeRule::MiddleOfTheNight);
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config)
+            .calculate()?;
+        assert_eq!(prayer_times.fajr, expected_time(4, 42, 00));
+        assert!(!prayer_times.is_adjusted(Prayer::Fajr));
+        assert!(!prayer_times.is_adjusted(Prayer::Ishaa));
+        Ok(())
+    }
+    #[test]
+    fn prayer_offsets() -> Result<(), crate::Error> {
+        let offsets = Offsets {
+            imsak: 0.0,
+            fajr: 2.0,
+            sherook: -3.0,
+            // 30 seconds earlier
+            dohr: -0.5,
+            asr: 0.0,
+            maghreb: 1.0,
+            ishaa: 1.0,
+            midnight: 0.0,
+        };
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().offsets(offsets))
+            .calculate()?;
+
+        assert_eq!(prayer_times.fajr, expected_time(4, 44, 00));
+        assert_eq!(prayer_times.sherook, expected_time(5, 56, 00));
+        assert_eq!(prayer_times.dohr, expected_time(12, 3, 00));
+        assert_eq!(prayer_times.asr, expected_time(15, 10, 00));
+        assert_eq!(prayer_times.maghreb, expected_time(18, 10, 00));
+        assert_eq!(prayer_times.ishaa, expected_time(19, 19, 00));
+        Ok(())
+    }
+    fn prayer_times_rounded(rounding: Rounding) -> Result<PrayerTimes, crate::Error> {
+        PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().rounding(rounding))
+            .calculate()
+    }
+    #[test]
+    fn rounding_policy() -> Result<(), crate::Error> {
+        let prayer_times = prayer_times_rounded(Rounding::Up)?;
+        assert_eq!(prayer_times.fajr, expected_time(4, 42, 00));
+        assert_eq!(prayer_times.sherook, expected_time(6, 0, 00));
+        assert_eq!(prayer_times.maghreb, expected_time(18, 9, 00));
+
+        let prayer_times = prayer_times_rounded(Rounding::Down)?;
+        assert_eq!(prayer_times.fajr, expected_time(4, 41, 00));
+        assert_eq!(prayer_times.sherook, expected_time(5, 59, 00));
+        assert_eq!(prayer_times.maghreb, expected_time(18, 8, 00));
+
+        // Prayers start rounded up, Sherook is rounded down
+        let prayer_times = prayer_times_rounded(Rounding::Safe)?;
+        assert_eq!(prayer_times.fajr, expected_time(4, 42, 00));
+        assert_eq!(prayer_times.sherook, expected_time(5, 59, 00));
+        assert_eq!(prayer_times.dohr, expected_time(12, 4, 00));
+        assert_eq!(prayer_times.maghreb, expected_time(18, 9, 00));
+
+        let prayer_times = prayer_times_rounded(Rounding::None)?;
+        assert_eq!(prayer_times.fajr, expected_time(4, 41, 49));
+        assert_eq!(prayer_times.sherook, expected_time(5, 59, 8));
+        assert_eq!(prayer_times.maghreb, expected_time(18, 8, 31));
+        Ok(())
+    }
+    #[test]
+    fn elevation() -> Result<(), crate::Error> {
+        // Sherook is earlier and Maghreb is later from the top of a 300 m building
+        let prayer_times = PrayerSchedule::new(city().with_elevation(300.0), timezone()?)
+            .on(date())?
+            .with_config(config())
+            .calculate()?;
+        assert_eq!(prayer_times.sherook, expected_time(5, 57, 00));
+        assert_eq!(prayer_times.dohr, expected_time(12, 4, 00));
+        assert_eq!(prayer_times.maghreb, expected_time(18, 11, 00));
+        // Twilight angles are below the lowered horizon too
+        assert_eq!(prayer_times.fajr, expected_time(4, 39, 00));
+        assert_eq!(prayer_times.ishaa, expected_time(19, 20, 00));
+        Ok(())
+    }
+    #[test]
+    fn refraction() -> Result<(), crate::Error> {
+        // Without refraction the sun sets a couple of minutes earlier
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().refraction(Refraction::None))
+            .calculate()?;
+        assert_eq!(prayer_times.sherook, expected_time(6, 1, 00));
+        assert_eq!(prayer_times.maghreb, expected_time(18, 6, 00));
+        assert_eq!(prayer_times.fajr, expected_time(4, 42, 00));
+
+        // A twilight angle close to the horizon is refracted too
+        let maghreb = |refraction| {
+            PrayerSchedule::new(city(), timezone()?)
+                .on(date())?
+                .with_config(config().maghreb_angle(0.25).refraction(refraction))
+                .calculate()
+                .map(|prayer_times| prayer_times.maghreb)
+        };
+        assert_eq!(maghreb(Refraction::None)?, expected_time(18, 6, 00));
+        assert_eq!(maghreb(Refraction::Standard)?, expected_time(18, 7, 00));
+        Ok(())
+    }
+    #[test]
+    fn imsak() -> Result<(), crate::Error> {
+        let prayer_times = prayer_times_on()?;
+        assert_eq!(prayer_times.imsak, expected_time(4, 32, 00));
+
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().imsak(Imsak::Angle(22.0)))
+            .calculate()?;
+        assert_eq!(prayer_times.imsak, expected_time(4, 34, 00));
+        Ok(())
+    }
+    #[test]
+    fn imsak_is_optional() -> Result<(), crate::Error> {
+        let prayer_times = prayer_times_at((4, 35, 0))?;
+        assert_eq!(prayer_times.current(), Prayer::Ishaa);
+        assert_eq!(prayer_times.next(), Prayer::Fajr);
+
+        let events = OptionalEvents {
+            imsak: true,
+            ..Default::default()
+        };
+        let prayer_times_at = |time: (i8, i8, i8)| {
+            PrayerSchedule::new(city(), timezone()?)
+                .at(date().at(time.0, time.1, time.2, 0))
+                .with_config(config().optional_events(events))
+                .calculate()
+        };
+        let prayer_times = prayer_times_at((4, 35, 0))?;
+        assert_eq!(prayer_times.current(), Prayer::Imsak);
+        assert_eq!(prayer_times.next(), Prayer::Fajr);
+
+        let prayer_times = prayer_times_at((3, 0, 0))?;
+        assert_eq!(prayer_times.current(), Prayer::Ishaa);
+        assert_eq!(prayer_times.next(), Prayer::Imsak);
+
+        let prayer_times = prayer_times_at((22, 0, 0))?;
+        assert_eq!(prayer_times.current(), Prayer::Ishaa);
+        assert_eq!(prayer_times.next(), Prayer::FajrTomorrow);
+        Ok(())
+    }
+    #[test]
+    fn makruh_windows() -> Result<(), crate::Error> {
+        let prayer_times = prayer_times_on()?;
+        let windows = prayer_times.makruh_windows();
+        assert_eq!(
+            windows,
+            vec![
+                (
+                    Makruh::Sunrise,
+                    expected_time(5, 59, 00)..expected_time(6, 14, 00)
+                ),
+                (
+                    Makruh::Zawal,
+                    expected_time(11, 54, 00)..expected_time(12, 4, 00)
+                ),
+                (
+                    Makruh::Sunset,
+                    expected_time(17, 54, 00)..expected_time(18, 9, 00)
+                ),
+            ]
+        );
+        assert_eq!(
+            prayer_times.makruh_at(expected_time(6, 0, 00)),
+            Some(Makruh::Sunrise)
+        );
+        assert_eq!(prayer_times.makruh_at(expected_time(9, 0, 00)), None);
+        assert_eq!(prayer_times.makruh_at(expected_time(18, 9, 00)), None);
+        Ok(())
+    }
+    #[test]
+    fn makruh_windows_by_altitude() -> Result<(), crate::Error> {
+        let extents = MakruhExtents {
+            sunrise: Extent::Altitude(4.0),
+            zawal: Extent::Altitude(1.0),
+            sunset: Extent::Altitude(4.0),
+        };
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().makruh(extents))
+            .calculate()?;
+        let windows = prayer_times.makruh_windows();
+        assert_eq!(windows[0].1.end, expected_time(6, 19, 00));
+        assert_eq!(windows[1].1.start, expected_time(11, 53, 00));
+        assert_eq!(windows[2].1.start, expected_time(17, 49, 00));
+        Ok(())
+    }
+    #[test]
+    fn prayer_windows() -> Result<(), crate::Error> {
+        let prayer_times = prayer_times_on()?;
+        let window = |prayer| prayer_times.window(prayer).unwrap();
+
+        assert_eq!(window(Prayer::Fajr).end, prayer_times.sherook);
+        assert_eq!(window(Prayer::Dohr).start, prayer_times.dohr);
+        assert_eq!(window(Prayer::Dohr).end, prayer_times.asr);
+        assert_eq!(window(Prayer::Asr).end, prayer_times.maghreb);
+        assert_eq!(
+            window(Prayer::Asr).preferred_end,
+            Some(expected_time(16, 21, 00))
+        );
+        assert_eq!(window(Prayer::Maghreb).end, prayer_times.ishaa);
+        assert_eq!(window(Prayer::Ishaa).end, prayer_times.midnight);
+        assert_eq!(window(Prayer::Ishaa).preferred_end, None);
+        assert_eq!(prayer_times.window(Prayer::Sherook), None);
+
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().ishaa_end(IshaaEnd::Fajr))
+            .calculate()?;
+        let ishaa = prayer_times.window(Prayer::Ishaa).unwrap();
+        assert_eq!(ishaa.end, prayer_times.fajr_tomorrow);
+        assert_eq!(ishaa.preferred_end, Some(prayer_times.midnight));
+
+        // Hanafi Asr starts when shadows are twice their length
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(Config::new().with(Method::Singapore, Madhab::Hanafi))
+            .calculate()?;
+        let asr = prayer_times.window(Prayer::Asr).unwrap();
+        assert_eq!(asr.preferred_end, None);
+        Ok(())
+    }
+    #[test]
+    fn voluntary_prayers() -> Result<(), crate::Error> {
+        let prayer_times = prayer_times_on()?;
+        assert_eq!(prayer_times.ishraq, expected_time(6, 19, 00));
+        // a quarter of the day after Sherook
+        assert_eq!(prayer_times.duha, expected_time(9, 1, 00));
+        let duha = prayer_times.window(Prayer::Duha).unwrap();
+        assert_eq!(duha.end, expected_time(11, 54, 00));
+
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().voluntary(Extent::Minutes(15.0), Extent::Minutes(30.0)))
+            .calculate()?;
+        assert_eq!(prayer_times.ishraq, expected_time(6, 14, 00));
+        assert_eq!(prayer_times.duha, expected_time(6, 29, 00));
+
+        // ignored by default
+        let prayer_times = prayer_times_at((6, 25, 0))?;
+        assert_eq!(prayer_times.current(), Prayer::Sherook);
+        assert_eq!(prayer_times.next(), Prayer::Dohr);
+
+        let events = OptionalEvents {
+            ishraq: true,
+            duha: true,
+            ..Default::default()
+        };
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .at(date().at(6, 25, 0, 0))
+            .with_config(config().optional_events(events))
+            .calculate()?;
+        assert_eq!(prayer_times.current(), Prayer::Ishraq);
+        assert_eq!(prayer_times.next(), Prayer::Duha);
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .at(date().at(9, 5, 0, 0))
+            .with_config(config().optional_events(events))
+            .calculate()?;
+        assert_eq!(prayer_times.current(), Prayer::Duha);
+        assert_eq!(prayer_times.next(), Prayer::Dohr);
+        Ok(())
+    }
+    #[test]
+    fn midnight_methods() -> Result<(), crate::Error> {
+        let prayer_times = prayer_times_on()?;
+        assert_eq!(prayer_times.first_third_of_night, expected_time(21, 40, 00));
+        assert_eq!(prayer_times.midnight, expected_time(23, 25, 00));
+        assert_eq!(
+            prayer_times.last_third_of_night,
+            date().at(1, 11, 00, 0) + 1.days()
+        );
+
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().midnight_method(MidnightMethod::MaghrebToSherook))
+            .calculate()?;
+        assert_eq!(prayer_times.midnight, date().at(0, 4, 00, 0) + 1.days());
+        Ok(())
+    }
+    #[test]
+    fn maghreb_angle() -> Result<(), crate::Error> {
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(Config::new().with(Method::Jafari, Madhab::Shafi))
+            .calculate()?;
+        assert_eq!(prayer_times.maghreb, expected_time(18, 21, 00));
+        assert_eq!(prayer_times.fajr, expected_time(4, 58, 00));
+        assert_eq!(prayer_times.ishaa, expected_time(19, 2, 00));
+
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(Config::new().with(Method::Tehran, Madhab::Shafi))
+            .calculate()?;
+        assert_eq!(prayer_times.maghreb, expected_time(18, 23, 00));
+        Ok(())
+    }
+    #[test]
+    fn kemenag_jakarta() -> Result<(), crate::Error> {
+        // jadwalsholat.org, Jakarta Pusat 6°10' LS 106°49' BT
+        let jakarta = Location::new(-6.1667, 106.8167);
+        let prayer_times = PrayerSchedule::new(jakarta, timezone()?)
+            .on(date())?
+            .with_config(Config::new().with(Method::Indonesia, Madhab::Shafi))
+            .calculate()?;
+
+        // The published ephemeris differs by less than a minute, a rounded time may be a minute off
+        let published = [
+            (prayer_times.fajr, expected_time(4, 42, 00)),
+            (prayer_times.sherook, expected_time(5, 56, 00)),
+            (prayer_times.dohr, expected_time(12, 5, 00)),
+            (prayer_times.asr, expected_time(15, 10, 00)),
+            (prayer_times.maghreb, expected_time(18, 10, 00)),
+            (prayer_times.ishaa, expected_time(19, 19, 00)),
+        ];
+        for (time, expected) in published {
+            let difference = time.duration_since(expected).abs();
+            assert!(
+                difference <= SignedDuration::from_mins(1),
+                "{time} != {expected}"
+            );
+        }
+
+        // 2min of ihtiyat, Sherook 2min earlier, then rounded up
+        let raw = PrayerSchedule::new(jakarta, timezone()?)
+            .on(date())?
+            .with_config(
+                Config::new()
+                    .with(Method::Indonesia, Madhab::Shafi)
+                    .offsets(Offsets::default())
+                    .rounding(Rounding::None),
+            )
+            .calculate()?;
+        let ihtiyat = |time: civil::DateTime, minutes: i64| -> Result<_, crate::Error> {
+            let time = time.checked_add(SignedDuration::from_mins(minutes))?;
+            Ok(time.round(
+                civil::DateTimeRound::new()
+                    .smallest(Unit::Minute)
+                    .mode(RoundMode::Ceil),
+            )?)
+        };
+        assert_eq!(prayer_times.fajr, ihtiyat(raw.fajr, 2)?);
+        assert_eq!(prayer_times.sherook, ihtiyat(raw.sherook, -2)?);
+        assert_eq!(prayer_times.dohr, ihtiyat(raw.dohr, 2)?);
+        assert_eq!(prayer_times.asr, ihtiyat(raw.asr, 2)?);
+        assert_eq!(prayer_times.maghreb, ihtiyat(raw.maghreb, 2)?);
+        assert_eq!(prayer_times.ishaa, ihtiyat(raw.ishaa, 2)?);
+        Ok(())
+    }
+    #[test]
+    fn moonsighting_committee() -> Result<(), crate::Error> {
+        let config = Config::new().with(Method::MoonsightingCommittee, Madhab::Shafi);
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config)
+            .calculate()?;
+        // 77.5 minutes before Sherook, and 74.9 minutes after sunset
+        assert_eq!(prayer_times.fajr, expected_time(4, 42, 00));
+        assert_eq!(prayer_times.dohr, expected_time(12, 9, 00));
+        assert_eq!(prayer_times.maghreb, expected_time(18, 12, 00));
+        assert_eq!(prayer_times.ishaa, expected_time(19, 23, 00));
+
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config.shafaq(Shafaq::Ahmer))
+            .calculate()?;
+        assert_eq!(prayer_times.ishaa, expected_time(19, 10, 00));
+
+        // A seventh of the night above 55°
+        let date = civil::date(2025, 6, 21);
+        let prayer_times =
+            PrayerSchedule::new(Location::new(59.91, 10.75), TimeZone::get("Europe/Oslo")?)
+                .on(date)?
+                .with_config(config)
+                .calculate()?;
+        assert_eq!(prayer_times.fajr, date.at(3, 9, 00, 0));
+        assert_eq!(prayer_times.ishaa, date.at(23, 28, 00, 0));
+        Ok(())
+    }
+    #[test]
+    fn asr_shadow_factor() -> Result<(), crate::Error> {
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().asr_shadow(2.0))
+            .calculate()?;
+        // same as the Hanafi Asr
+        assert_eq!(prayer_times.asr, expected_time(16, 21, 00));
+
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .on(date())?
+            .with_config(config().asr_preferred_shadow(1.5))
+            .calculate()?;
+        let asr = prayer_times.window(Prayer::Asr).unwrap();
+        assert_eq!(asr.start, expected_time(15, 10, 00));
+        assert_eq!(asr.preferred_end, Some(expected_time(15, 53, 00)));
+        Ok(())
+    }
+    #[test]
+    fn ramadan_ishaa_interval() -> Result<(), crate::Error> {
+        // 1 Shawwal 1446 was announced on 2025-03-30, a day before the tabular calendar
+        let date = civil::date(2025, 3, 30);
+        let makkah = Location::new(21.4225, 39.8262);
+        let schedule = |config| {
+            PrayerSchedule::new(makkah, TimeZone::get("Asia/Riyadh")?)
+                .on(date)?
+                .with_config(config)
+                .calculate()
+        };
+        let umm_al_qura = Config::new().with(Method::UmmAlQura, Madhab::Shafi);
+        let prayer_times = schedule(umm_al_qura)?;
+        let interval = prayer_times.ishaa.duration_since(prayer_times.maghreb);
+        assert_eq!(interval, SignedDuration::from_mins(90));
+
+        let schedule =
+            |calendar, correction| schedule(umm_al_qura.hijri_calendar(calendar, correction));
+        let prayer_times = schedule(Calendar::default(), 0)?;
+        let interval = prayer_times.ishaa.duration_since(prayer_times.maghreb);
+        assert_eq!(interval, SignedDuration::from_mins(120));
+
+        let prayer_times = schedule(Calendar::default(), 1)?;
+        let interval = prayer_times.ishaa.duration_since(prayer_times.maghreb);
+        assert_eq!(interval, SignedDuration::from_mins(90));
+
+        let prayer_times = schedule(Calendar::UmmAlQura, 0)?;
+        let interval = prayer_times.ishaa.duration_since(prayer_times.maghreb);
+        assert_eq!(interval, SignedDuration::from_mins(90));
+        Ok(())
+    }
+    #[test]
+    fn ramadan_outside_umm_al_qura() -> Result<(), crate::Error> {
+        // The Umm al-Qura table ends with 1600 AH, Ramadan is then found with the tabular calendar
+        let makkah = Location::new(21.4225, 39.8262);
+        let config = Config::new().with(Method::UmmAlQura, Madhab::Shafi);
+        let tabular = |month| Calendar::default().to_gregorian(&HijriDate::new(1620, month, 10)?);
+        for (date, interval) in [(tabular(9)?, 120), (tabular(10)?, 90)] {
+            let prayer_times = PrayerSchedule::new(makkah, TimeZone::get("Asia/Riyadh")?)
+                .on(date)?
+                .with_config(config)
+                .calculate()?;
+            let ishaa = prayer_times.ishaa.duration_since(prayer_times.maghreb);
+            assert_eq!(ishaa, SignedDuration::from_mins(interval));
+        }
+        Ok(())
+    }
+    #[test]
+    fn jumuah() -> Result<(), crate::Error> {
+        let prayer_times = prayer_times_on()?;
+        assert_eq!(prayer_times.jumuah, None);
+        assert_eq!(prayer_times.time(Prayer::Jumuah), prayer_times.dohr);
+        assert_eq!(prayer_times.window(Prayer::Jumuah), None);
+        assert_eq!(prayer_times.name_of(Prayer::Dohr), "Dohr");
+
+        // Dohr starts at 12:03, Jumuah is at Dohr by default
+        let friday = civil::date(2025, 3, 14);
+        let prayer_times = PrayerSchedule::new(city(), timezone()?)
+            .at(friday.at(12, 30, 0, 0))
+            .with_config(config())
+            .calculate()?;
+        assert_eq!(prayer_times.jumuah, Some(prayer_times.dohr));
+        assert_eq!(prayer_times.name_of(Prayer::Dohr), "Jumua");
+        assert_eq!(prayer_times.current(), Prayer::Dohr);
+        assert_eq!(prayer_times.next(), Prayer::Asr);
+        Ok(())
+    }
+    fn friday_at(time: (i8, i8), jumuah: (i8, i8)) -> Result<PrayerTimes, crate::Error> {
+        let jumuah = civil::time(jumuah.0, jumuah.1, 0, 0);
+        PrayerSchedule::new(city(), timezone()?)
+            .at(civil::date(2025, 3, 14).at(time.0, time.1, 0, 0))
+            .with_config(config().jumuah(jumuah))
+            .calculate()
+    }
+    #[test]
+    fn jumuah_after_dohr() -> Result<(), crate::Error> {
+        let prayer_times = friday_at((12, 30), (12, 45))?;
+        assert_eq!(prayer_times.name_of(Prayer::Dohr), "Dohr");
+        assert_eq!(prayer_times.current(), Prayer::Dohr);
+        assert_eq!(prayer_times.next(), Prayer::Jumuah);
+        assert_eq!(prayer_times.time_remaining(), (0, 15));
+        let window = prayer_times.window(Prayer::Jumuah);
+        assert_eq!(window.map(|window| window.end), Some(prayer_times.asr));
+
+        let prayer_times = friday_at((13, 0), (12, 45))?;
+        assert_eq!(prayer_times.current(), Prayer::Dohr);
+        assert_eq!(prayer_times.next(), Prayer::Asr);
+        Ok(())
+    }
+    #[test]
+    fn jumuah_before_dohr() -> Result<(), crate::Error> {
+        let prayer_times = friday_at((11, 30), (11, 45))?;
+        assert_eq!(prayer_times.current(), Prayer::Sherook);
+        assert_eq!(prayer_times.next(), Prayer::Jumuah);
+
+        let prayer_times = friday_at((11, 50), (11, 45))?;
+        assert_eq!(prayer_times.current(), Prayer::Sherook);
+        assert_eq!(prayer_times.next(), Prayer::Dohr);
+
+        let prayer_times = friday_at((12, 30), (11, 45))?;
+        assert_eq!(prayer_times.current(), Prayer::Dohr);
+        assert_eq!(prayer_times.next(), Prayer::Asr);
+
+        let prayer_times = friday_at((11, 30), (11, 45)).map(ZonedPrayerTimes::new)?;
+        assert_eq!(prayer_times.next(), Prayer::Jumuah);
+        Ok(())
+    }
 }
@@ -0,0 +1,19 @@
+use jiff::civil;
+
+/// When Ishaa ends
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum IshaaEnd {
+    /// Ishaa ends at midnight
+    Midnight,
+    /// Ishaa ends at Fajr, but it is preferred to pray it before midnight
+    Fajr,
+}
+
+/// Start and end of a prayer
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct PrayerWindow {
+    pub start: civil::DateTime,
+    pub end: civil::DateTime,
+    /// end of the preferred (ikhtiyari) time, if the prayer has one
+    pub preferred_end: Option<civil::DateTime>,
+}